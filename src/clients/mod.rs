@@ -1,3 +1,2 @@
 pub mod nrf;
-
-pub use nrf::*;
+pub mod oauth2;
@@ -1,7 +1,12 @@
 use anyhow::{Context, Result};
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+use crate::config::NrfConfig;
 use crate::types::NfProfile;
+use super::oauth2::AccessTokenProvider;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,30 +24,288 @@ pub struct SearchResult {
     pub nf_instances: Vec<NfProfile>,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct PatchItem {
+    pub op: &'static str,
+    pub path: &'static str,
+    pub value: serde_json::Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeartbeatOutcome {
+    Acknowledged { heart_beat_timer: Option<u32> },
+    NotRegistered,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NrfEndpointStatus {
+    pub uri: String,
+    pub priority: u32,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Clone, Debug)]
+struct NrfEndpoint {
+    uri: String,
+    priority: u32,
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+impl NrfEndpoint {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.is_none_or(|until| now >= until)
+    }
+}
+
 #[derive(Clone)]
 pub struct NrfClient {
     client: Client,
-    nrf_uri: String,
+    endpoints: Arc<Mutex<Vec<NrfEndpoint>>>,
+    unhealthy_threshold: u32,
+    unhealthy_duration: Duration,
+    max_redirects: usize,
+    token_provider: Option<AccessTokenProvider>,
 }
 
 impl NrfClient {
-    pub fn new(nrf_uri: String, client: Client) -> Self {
-        Self { client, nrf_uri }
+    pub fn new(config: &NrfConfig, client: Client) -> Self {
+        let endpoints = config
+            .endpoints
+            .iter()
+            .map(|endpoint| NrfEndpoint {
+                uri: endpoint.uri.clone(),
+                priority: endpoint.priority,
+                consecutive_failures: 0,
+                unhealthy_until: None,
+            })
+            .collect();
+
+        Self {
+            client,
+            endpoints: Arc::new(Mutex::new(endpoints)),
+            unhealthy_threshold: config.unhealthy_threshold.max(1),
+            unhealthy_duration: Duration::from_secs(config.unhealthy_seconds),
+            max_redirects: config.max_redirects,
+            token_provider: None,
+        }
+    }
+
+    pub fn with_token_provider(mut self, token_provider: AccessTokenProvider) -> Self {
+        self.token_provider = Some(token_provider);
+        self
+    }
+
+    pub fn issues_access_tokens(&self) -> bool {
+        self.token_provider.is_some()
+    }
+
+    async fn authorize(&self, request: RequestBuilder, nrf_uri: &str, scope: &str) -> Result<RequestBuilder> {
+        match &self.token_provider {
+            Some(provider) => {
+                let token_endpoint = provider.token_endpoint(nrf_uri);
+                let token = provider
+                    .get_token(&token_endpoint, "NRF", scope)
+                    .await
+                    .with_context(|| format!("Failed to obtain access token for NRF from {}", token_endpoint))?;
+                Ok(request.bearer_auth(token))
+            }
+            None => Ok(request),
+        }
+    }
+
+    pub async fn access_token(&self, target_nf_type: &str, scope: &str) -> Result<String> {
+        let Some(provider) = &self.token_provider else {
+            return Err(anyhow::anyhow!("NRF access tokens are not enabled"));
+        };
+
+        let endpoints = self.ordered_endpoints();
+        if endpoints.is_empty() {
+            return Err(anyhow::anyhow!("No NRF endpoints configured"));
+        }
+
+        let mut last_error = None;
+
+        for base_uri in endpoints {
+            let token_endpoint = provider.token_endpoint(&base_uri);
+            match provider.get_token(&token_endpoint, target_nf_type, scope).await {
+                Ok(token) => {
+                    self.record_success(&base_uri);
+                    return Ok(token);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to obtain access token from {}: {:#}, trying next NRF", token_endpoint, e);
+                    self.record_failure(&base_uri);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All NRF endpoints failed")))
+    }
+
+    fn configured_nrf(&self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|endpoint| Url::parse(&endpoint.uri).is_ok_and(|uri| uri.origin() == url.origin()))
+            .map(|endpoint| endpoint.uri.clone())
+    }
+
+    pub fn endpoint_status(&self) -> Vec<NrfEndpointStatus> {
+        let now = Instant::now();
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|endpoint| NrfEndpointStatus {
+                uri: endpoint.uri.clone(),
+                priority: endpoint.priority,
+                healthy: endpoint.is_healthy(now),
+                consecutive_failures: endpoint.consecutive_failures,
+            })
+            .collect()
+    }
+
+    fn ordered_endpoints(&self) -> Vec<String> {
+        let now = Instant::now();
+        let endpoints = self.endpoints.lock().unwrap();
+
+        let (healthy, unhealthy): (Vec<&NrfEndpoint>, Vec<&NrfEndpoint>) =
+            endpoints.iter().partition(|endpoint| endpoint.is_healthy(now));
+
+        healthy
+            .into_iter()
+            .chain(unhealthy)
+            .map(|endpoint| endpoint.uri.clone())
+            .collect()
+    }
+
+    fn record_success(&self, uri: &str) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        if let Some(endpoint) = endpoints.iter_mut().find(|endpoint| endpoint.uri == uri) {
+            if endpoint.unhealthy_until.is_some() {
+                tracing::info!("NRF {} is reachable again", uri);
+            }
+            endpoint.consecutive_failures = 0;
+            endpoint.unhealthy_until = None;
+        }
+    }
+
+    fn record_failure(&self, uri: &str) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        if let Some(endpoint) = endpoints.iter_mut().find(|endpoint| endpoint.uri == uri) {
+            endpoint.consecutive_failures += 1;
+            if endpoint.consecutive_failures >= self.unhealthy_threshold {
+                if endpoint.unhealthy_until.is_none() {
+                    tracing::warn!(
+                        "Marking NRF {} unhealthy after {} consecutive failures",
+                        uri,
+                        endpoint.consecutive_failures
+                    );
+                }
+                endpoint.unhealthy_until = Some(Instant::now() + self.unhealthy_duration);
+            }
+        }
+    }
+
+    async fn send<F>(&self, operation: &str, scope: &str, path: &str, build: F) -> Result<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let endpoints = self.ordered_endpoints();
+        if endpoints.is_empty() {
+            return Err(anyhow::anyhow!("No NRF endpoints configured"));
+        }
+
+        let mut last_error = None;
+
+        for base_uri in endpoints {
+            let mut url = format!("{}{}", base_uri, path);
+            let mut redirects = 0;
+
+            let outcome = loop {
+                let request = if let Some(nrf_uri) = self.configured_nrf(&url) {
+                    match self.authorize(build(&url), &nrf_uri, scope).await {
+                        Ok(request) => request,
+                        Err(e) => break Err(e.context(format!("Failed to authorize {} request to NRF {}", operation, base_uri))),
+                    }
+                } else {
+                    tracing::debug!("Not sending NRF access token to unconfigured redirect target {}", url);
+                    build(&url)
+                };
+
+                let response = match request.send().await {
+                    Ok(response) => response,
+                    Err(e) => break Err(anyhow::Error::new(e).context(format!("Failed to send {} request to NRF {}", operation, base_uri))),
+                };
+
+                let status = response.status();
+
+                if matches!(status, StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT) {
+                    let location = response
+                        .headers()
+                        .get(reqwest::header::LOCATION)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.to_string());
+
+                    match location {
+                        Some(location) if redirects < self.max_redirects => {
+                            let target = match resolve_redirect(&url, &location) {
+                                Ok(target) => target,
+                                Err(e) => break Err(e.context(format!("NRF {} returned an invalid Location", base_uri))),
+                            };
+                            tracing::info!("NRF {} redirected {} request to {}", base_uri, operation, target);
+                            url = target;
+                            redirects += 1;
+                            continue;
+                        }
+                        Some(_) => break Err(anyhow::anyhow!("Too many NRF redirects for {} request", operation)),
+                        None => break Err(anyhow::anyhow!("NRF {} returned {} without Location", base_uri, status)),
+                    }
+                }
+
+                if status.is_server_error() {
+                    let error_body = response.text().await.unwrap_or_default();
+                    break Err(anyhow::anyhow!(
+                        "NRF {} {} request failed with status {}: {}",
+                        base_uri,
+                        operation,
+                        status,
+                        error_body
+                    ));
+                }
+
+                break Ok(response);
+            };
+
+            match outcome {
+                Ok(response) => {
+                    self.record_success(&base_uri);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    tracing::warn!("{:#}, trying next NRF", e);
+                    self.record_failure(&base_uri);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All NRF endpoints failed")))
     }
 
     pub async fn register(&self, profile: &NfProfile) -> Result<NfProfile> {
-        let url = format!(
-            "{}/nnrf-nfm/v1/nf-instances/{}",
-            self.nrf_uri, profile.nf_instance_id
-        );
+        let path = format!("/nnrf-nfm/v1/nf-instances/{}", profile.nf_instance_id);
 
         let response = self
-            .client
-            .put(&url)
-            .json(profile)
-            .send()
-            .await
-            .context("Failed to send registration request to NRF")?;
+            .send("registration", "nnrf-nfm", &path, |url| self.client.put(url).json(profile))
+            .await?;
 
         match response.status() {
             StatusCode::CREATED | StatusCode::OK => {
@@ -70,15 +333,14 @@ impl NrfClient {
     }
 
     pub async fn discover(&self, params: &NfDiscoveryParams) -> Result<Vec<NfProfile>> {
-        let url = format!("{}/nnrf-disc/v1/nf-instances", self.nrf_uri);
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("target-nf-type", &params.target_nf_type)
+            .finish();
+        let path = format!("/nnrf-disc/v1/nf-instances?{}", query);
 
         let response = self
-            .client
-            .get(&url)
-            .query(&[("target-nf-type", &params.target_nf_type)])
-            .send()
-            .await
-            .context("Failed to send discovery request to NRF")?;
+            .send("discovery", "nnrf-disc", &path, |url| self.client.get(url))
+            .await?;
 
         match response.status() {
             StatusCode::OK => {
@@ -101,17 +363,11 @@ impl NrfClient {
     }
 
     pub async fn deregister(&self, nf_instance_id: &str) -> Result<()> {
-        let url = format!(
-            "{}/nnrf-nfm/v1/nf-instances/{}",
-            self.nrf_uri, nf_instance_id
-        );
+        let path = format!("/nnrf-nfm/v1/nf-instances/{}", nf_instance_id);
 
         let response = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .context("Failed to send deregistration request to NRF")?;
+            .send("deregistration", "nnrf-nfm", &path, |url| self.client.delete(url))
+            .await?;
 
         match response.status() {
             StatusCode::NO_CONTENT | StatusCode::OK => {
@@ -129,28 +385,71 @@ impl NrfClient {
         }
     }
 
-    pub async fn heartbeat(&self, profile: &NfProfile) -> Result<()> {
-        let url = format!(
-            "{}/nnrf-nfm/v1/nf-instances/{}",
-            self.nrf_uri, profile.nf_instance_id
-        );
+    pub async fn unsubscribe(&self, nrf_uri: &str, subscription_id: &str) -> Result<()> {
+        let url = format!("{}/nnrf-nfm/v1/subscriptions/{}", nrf_uri.trim_end_matches('/'), subscription_id);
 
-        let response = self
-            .client
-            .patch(&url)
-            .json(profile)
+        let request = match self.configured_nrf(&url) {
+            Some(configured_uri) => self.authorize(self.client.delete(&url), &configured_uri, "nnrf-nfm").await?,
+            None => self.client.delete(&url),
+        };
+
+        let response = request
             .send()
             .await
-            .context("Failed to send heartbeat request to NRF")?;
+            .with_context(|| format!("Failed to send unsubscribe request to NRF {}", nrf_uri))?;
 
         match response.status() {
-            StatusCode::OK | StatusCode::NO_CONTENT | StatusCode::CREATED => {
-                tracing::debug!(
-                    "Heartbeat sent successfully for NF instance {}",
-                    profile.nf_instance_id
-                );
+            StatusCode::NO_CONTENT | StatusCode::OK | StatusCode::NOT_FOUND => {
+                tracing::info!("Cancelled NRF subscription {} at {}", subscription_id, nrf_uri);
                 Ok(())
             }
+            status => {
+                let error_body = response.text().await.unwrap_or_default();
+                Err(anyhow::anyhow!(
+                    "NRF unsubscribe failed with status {}: {}",
+                    status,
+                    error_body
+                ))
+            }
+        }
+    }
+
+    pub async fn heartbeat(&self, nf_instance_id: &str, nf_status: &str) -> Result<HeartbeatOutcome> {
+        let path = format!("/nnrf-nfm/v1/nf-instances/{}", nf_instance_id);
+        let patch = vec![PatchItem {
+            op: "replace",
+            path: "/nfStatus",
+            value: serde_json::Value::String(nf_status.to_string()),
+        }];
+
+        let response = self
+            .send("heartbeat", "nnrf-nfm", &path, |url| {
+                self.client
+                    .patch(url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json-patch+json")
+                    .json(&patch)
+            })
+            .await?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT => {
+                tracing::debug!("Heartbeat acknowledged for NF instance {}", nf_instance_id);
+                Ok(HeartbeatOutcome::Acknowledged { heart_beat_timer: None })
+            }
+            StatusCode::OK => {
+                let heart_beat_timer = response
+                    .json::<NfProfile>()
+                    .await
+                    .ok()
+                    .and_then(|profile| profile.heart_beat_timer);
+
+                tracing::debug!("Heartbeat acknowledged for NF instance {}", nf_instance_id);
+                Ok(HeartbeatOutcome::Acknowledged { heart_beat_timer })
+            }
+            StatusCode::NOT_FOUND => {
+                tracing::warn!("NRF does not know NF instance {}, re-registration required", nf_instance_id);
+                Ok(HeartbeatOutcome::NotRegistered)
+            }
             status => {
                 let error_body = response.text().await.unwrap_or_default();
                 Err(anyhow::anyhow!(
@@ -162,3 +461,163 @@ impl NrfClient {
         }
     }
 }
+
+fn resolve_redirect(current: &str, location: &str) -> Result<String> {
+    let target = Url::parse(current)
+        .and_then(|current| current.join(location))
+        .with_context(|| format!("Unable to resolve redirect Location {}", location))?;
+    Ok(target.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NrfEndpointConfig;
+    use crate::services::metrics::Metrics;
+    use axum::extract::RawQuery;
+    use axum::http::HeaderMap;
+    use axum::response::IntoResponse;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+
+    fn config(uris: &[&str]) -> NrfConfig {
+        NrfConfig {
+            endpoints: uris
+                .iter()
+                .enumerate()
+                .map(|(index, uri)| NrfEndpointConfig {
+                    uri: uri.to_string(),
+                    priority: index as u32 + 1,
+                })
+                .collect(),
+            unhealthy_threshold: 3,
+            unhealthy_seconds: 30,
+            max_redirects: 3,
+        }
+    }
+
+    fn client() -> NrfClient {
+        NrfClient::new(&config(&["http://nrf-a.example:8000", "https://nrf-b.example"]), Client::new())
+    }
+
+    fn nrf_http_client() -> Client {
+        Client::builder().redirect(reqwest::redirect::Policy::none()).build().unwrap()
+    }
+
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        format!("http://{}", address)
+    }
+
+    type SeenRequests = Arc<Mutex<Vec<(Option<String>, Option<String>)>>>;
+
+    fn discovery_route(seen: SeenRequests) -> Router {
+        Router::new().route(
+            "/nnrf-disc/v1/nf-instances",
+            get(move |headers: HeaderMap, RawQuery(query): RawQuery| async move {
+                let authorization = headers
+                    .get(reqwest::header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                seen.lock().unwrap().push((authorization, query));
+                Json(serde_json::json!({ "nfInstances": [] }))
+            }),
+        )
+    }
+
+    #[test]
+    fn relative_redirect_resolves_against_current_url() {
+        assert_eq!(
+            resolve_redirect("http://nrf-a.example:8000/nnrf-disc/v1/nf-instances?target-nf-type=UDM", "/other/path")
+                .unwrap(),
+            "http://nrf-a.example:8000/other/path"
+        );
+        assert_eq!(
+            resolve_redirect("http://nrf-a.example:8000/nnrf-nfm/v1/nf-instances/1", "2").unwrap(),
+            "http://nrf-a.example:8000/nnrf-nfm/v1/nf-instances/2"
+        );
+        assert_eq!(
+            resolve_redirect("http://nrf-a.example:8000/a", "https://nrf-b.example/b").unwrap(),
+            "https://nrf-b.example/b"
+        );
+    }
+
+    #[test]
+    fn only_configured_nrf_origins_receive_tokens() {
+        let client = client();
+
+        assert_eq!(
+            client.configured_nrf("http://nrf-a.example:8000/nnrf-nfm/v1/nf-instances").as_deref(),
+            Some("http://nrf-a.example:8000")
+        );
+        assert_eq!(
+            client.configured_nrf("https://nrf-b.example:443/nnrf-disc/v1/nf-instances").as_deref(),
+            Some("https://nrf-b.example")
+        );
+        assert_eq!(client.configured_nrf("http://nrf-a.example:9000/nnrf-nfm/v1/nf-instances"), None);
+        assert_eq!(client.configured_nrf("https://attacker.example/nnrf-nfm/v1/nf-instances"), None);
+        assert_eq!(client.configured_nrf("not a url"), None);
+    }
+
+    #[tokio::test]
+    async fn token_failure_fails_over_to_the_next_nrf() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let router = discovery_route(seen.clone()).route(
+            "/oauth2/token",
+            post(|| async { Json(serde_json::json!({ "access_token": "token-b", "token_type": "Bearer", "expires_in": 3600 })) }),
+        );
+        let nrf_b = serve(router).await;
+
+        let provider = AccessTokenProvider::new(
+            Client::new(),
+            None,
+            "8b5e2f3c-0000-4000-8000-000000000001".to_string(),
+            Duration::from_secs(60),
+            Metrics::new(),
+        );
+        let client = NrfClient::new(&config(&["http://127.0.0.1:1", &nrf_b]), nrf_http_client())
+            .with_token_provider(provider);
+
+        let params = NfDiscoveryParams {
+            target_nf_type: "UDM".to_string(),
+            requester_nf_type: None,
+            service_names: None,
+        };
+        assert!(client.discover(&params).await.unwrap().is_empty());
+
+        assert_eq!(
+            seen.lock().unwrap().as_slice(),
+            &[(Some("Bearer token-b".to_string()), Some("target-nf-type=UDM".to_string()))]
+        );
+        let status = client.endpoint_status();
+        assert_eq!(status[0].consecutive_failures, 1);
+        assert_eq!(status[1].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn redirected_discovery_keeps_the_location_query() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let router = discovery_route(seen.clone()).route(
+            "/old/nnrf-disc/v1/nf-instances",
+            get(|RawQuery(query): RawQuery| async move {
+                let location = format!("/nnrf-disc/v1/nf-instances?{}", query.unwrap_or_default());
+                (StatusCode::TEMPORARY_REDIRECT, [(reqwest::header::LOCATION, location)]).into_response()
+            }),
+        );
+        let nrf = serve(router).await;
+
+        let client = NrfClient::new(&config(&[&format!("{}/old", nrf)]), nrf_http_client());
+        let params = NfDiscoveryParams {
+            target_nf_type: "UDM".to_string(),
+            requester_nf_type: None,
+            service_names: None,
+        };
+        client.discover(&params).await.unwrap();
+
+        assert_eq!(seen.lock().unwrap().as_slice(), &[(None, Some("target-nf-type=UDM".to_string()))]);
+    }
+}
@@ -0,0 +1,366 @@
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use crate::services::metrics::Metrics;
+
+const DEFAULT_EXPIRES_IN_SECONDS: u64 = 3600;
+const MAX_CACHED_TOKENS: usize = 1024;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccessTokenReq<'a> {
+    #[serde(rename = "grant_type")]
+    grant_type: &'static str,
+    nf_instance_id: &'a str,
+    nf_type: &'a str,
+    target_nf_type: &'a str,
+    scope: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AccessTokenRsp {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct TokenKey {
+    token_endpoint: String,
+    target_nf_type: String,
+    scope: String,
+}
+
+#[derive(Clone, Debug)]
+struct CachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct AccessTokenProvider {
+    client: Client,
+    token_endpoint: Option<String>,
+    nf_instance_id: String,
+    nf_type: String,
+    refresh_margin: Duration,
+    tokens: Arc<DashMap<TokenKey, CachedToken>>,
+    fetch_locks: Arc<DashMap<TokenKey, Arc<Mutex<()>>>>,
+    metrics: Metrics,
+}
+
+impl AccessTokenProvider {
+    pub fn new(
+        client: Client,
+        token_endpoint: Option<String>,
+        nf_instance_id: String,
+        refresh_margin: Duration,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            client,
+            token_endpoint,
+            nf_instance_id,
+            nf_type: "SCP".to_string(),
+            refresh_margin,
+            tokens: Arc::new(DashMap::new()),
+            fetch_locks: Arc::new(DashMap::new()),
+            metrics,
+        }
+    }
+
+    pub fn token_endpoint(&self, nrf_uri: &str) -> String {
+        self.token_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("{}/oauth2/token", nrf_uri.trim_end_matches('/')))
+    }
+
+    pub async fn get_token(&self, token_endpoint: &str, target_nf_type: &str, scope: &str) -> Result<String> {
+        let key = TokenKey {
+            token_endpoint: token_endpoint.to_string(),
+            target_nf_type: target_nf_type.to_uppercase(),
+            scope: scope.to_string(),
+        };
+        let now = Instant::now();
+
+        if let Some(cached) = self.tokens.get(&key).map(|entry| entry.clone()) {
+            if cached.expires_at > now + self.refresh_margin {
+                return Ok(cached.access_token);
+            }
+
+            if cached.expires_at > now {
+                self.spawn_refresh(key);
+                return Ok(cached.access_token);
+            }
+        }
+
+        self.fetch_coalesced(&key).await
+    }
+
+    fn spawn_refresh(&self, key: TokenKey) {
+        let lock = self.fetch_lock(&key);
+        let Ok(guard) = lock.try_lock_owned() else {
+            return;
+        };
+
+        let provider = self.clone();
+        tokio::spawn(async move {
+            let _guard = guard;
+            if let Err(e) = provider.fetch(&key).await {
+                tracing::warn!(
+                    "Failed to refresh access token for {} ({}): {}",
+                    key.target_nf_type,
+                    key.scope,
+                    e
+                );
+            }
+        });
+    }
+
+    async fn fetch_coalesced(&self, key: &TokenKey) -> Result<String> {
+        let lock = self.fetch_lock(key);
+        let _guard = lock.lock().await;
+
+        if let Some(cached) = self.tokens.get(key) {
+            if cached.expires_at > Instant::now() {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        self.fetch(key).await
+    }
+
+    fn fetch_lock(&self, key: &TokenKey) -> Arc<Mutex<()>> {
+        self.fetch_locks
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+
+    async fn fetch(&self, key: &TokenKey) -> Result<String> {
+        tracing::debug!(
+            "Requesting access token for target NF type {} with scope {}",
+            key.target_nf_type,
+            key.scope
+        );
+
+        let request = AccessTokenReq {
+            grant_type: "client_credentials",
+            nf_instance_id: &self.nf_instance_id,
+            nf_type: &self.nf_type,
+            target_nf_type: &key.target_nf_type,
+            scope: &key.scope,
+        };
+
+        let result = self.request_token(&key.token_endpoint, &request).await;
+        self.evict();
+        self.metrics.increment_counter(
+            "scp_oauth2_token_requests_total",
+            &[
+                ("target_nf_type", key.target_nf_type.as_str()),
+                ("result", if result.is_ok() { "success" } else { "failure" }),
+            ],
+        );
+
+        let token = result?;
+
+        if let Some(token_type) = &token.token_type {
+            if !token_type.eq_ignore_ascii_case("bearer") {
+                return Err(anyhow::anyhow!("Unsupported access token type: {}", token_type));
+            }
+        }
+
+        let expires_in = Duration::from_secs(token.expires_in.unwrap_or(DEFAULT_EXPIRES_IN_SECONDS));
+        self.tokens.insert(
+            key.clone(),
+            CachedToken {
+                access_token: token.access_token.clone(),
+                expires_at: Instant::now() + expires_in,
+            },
+        );
+
+        tracing::info!(
+            "Obtained access token for {} ({}), valid for {}s",
+            key.target_nf_type,
+            key.scope,
+            expires_in.as_secs()
+        );
+
+        Ok(token.access_token)
+    }
+
+    fn evict(&self) {
+        let now = Instant::now();
+        self.tokens.retain(|_, token| token.expires_at > now);
+
+        let excess = self.tokens.len().saturating_sub(MAX_CACHED_TOKENS);
+        if excess > 0 {
+            let mut by_expiry: Vec<(Instant, TokenKey)> = self
+                .tokens
+                .iter()
+                .map(|entry| (entry.expires_at, entry.key().clone()))
+                .collect();
+            by_expiry.sort_unstable_by_key(|(expires_at, _)| *expires_at);
+
+            for (_, key) in by_expiry.into_iter().take(excess) {
+                self.tokens.remove(&key);
+            }
+        }
+
+        self.fetch_locks
+            .retain(|key, lock| Arc::strong_count(lock) > 1 || self.tokens.contains_key(key));
+    }
+
+    async fn request_token(&self, token_endpoint: &str, request: &AccessTokenReq<'_>) -> Result<AccessTokenRsp> {
+        let response = self
+            .client
+            .post(token_endpoint)
+            .form(request)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send access token request to {}", token_endpoint))?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .context("Failed to parse NRF access token response"),
+            status => {
+                let error_body = response.text().await.unwrap_or_default();
+                Err(anyhow::anyhow!(
+                    "NRF access token request failed with status {}: {}",
+                    status,
+                    error_body
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(scope: &str) -> TokenKey {
+        TokenKey {
+            token_endpoint: "http://nrf.example/oauth2/token".to_string(),
+            target_nf_type: "UDM".to_string(),
+            scope: scope.to_string(),
+        }
+    }
+
+    #[test]
+    fn evict_drops_expired_tokens_and_idle_fetch_locks() {
+        let provider = AccessTokenProvider::new(
+            Client::new(),
+            None,
+            "8b5e2f3c-0000-4000-8000-000000000001".to_string(),
+            Duration::from_secs(60),
+            Metrics::new(),
+        );
+        let now = Instant::now();
+
+        for (scope, expires_at) in [("nudm-sdm", now + Duration::from_secs(60)), ("nudm-uecm", now)] {
+            provider.tokens.insert(
+                key(scope),
+                CachedToken {
+                    access_token: scope.to_string(),
+                    expires_at,
+                },
+            );
+            provider.fetch_lock(&key(scope));
+        }
+        let held = provider.fetch_lock(&key("nudm-ee"));
+        provider.fetch_lock(&key("nxyz-made-up"));
+
+        provider.evict();
+
+        assert!(provider.tokens.contains_key(&key("nudm-sdm")));
+        assert!(!provider.tokens.contains_key(&key("nudm-uecm")));
+        assert!(provider.fetch_locks.contains_key(&key("nudm-sdm")));
+        assert!(provider.fetch_locks.contains_key(&key("nudm-ee")));
+        assert!(!provider.fetch_locks.contains_key(&key("nudm-uecm")));
+        assert!(!provider.fetch_locks.contains_key(&key("nxyz-made-up")));
+        drop(held);
+    }
+
+    #[test]
+    fn evict_caps_the_token_cache() {
+        let provider = AccessTokenProvider::new(
+            Client::new(),
+            None,
+            "8b5e2f3c-0000-4000-8000-000000000001".to_string(),
+            Duration::from_secs(60),
+            Metrics::new(),
+        );
+        let now = Instant::now();
+
+        for index in 0..MAX_CACHED_TOKENS + 5 {
+            provider.tokens.insert(
+                key(&format!("scope-{}", index)),
+                CachedToken {
+                    access_token: String::new(),
+                    expires_at: now + Duration::from_secs(60 + index as u64),
+                },
+            );
+        }
+
+        provider.evict();
+
+        assert_eq!(provider.tokens.len(), MAX_CACHED_TOKENS);
+        assert!(!provider.tokens.contains_key(&key("scope-0")));
+        assert!(provider.tokens.contains_key(&key(&format!("scope-{}", MAX_CACHED_TOKENS + 4))));
+    }
+
+    #[test]
+    fn access_token_request_uses_grant_type_form_field() {
+        let request = AccessTokenReq {
+            grant_type: "client_credentials",
+            nf_instance_id: "8b5e2f3c-0000-4000-8000-000000000001",
+            nf_type: "SCP",
+            target_nf_type: "UDM",
+            scope: "nudm-sdm",
+        };
+
+        let built = Client::new()
+            .post("http://nrf.example/oauth2/token")
+            .form(&request)
+            .build()
+            .unwrap();
+        let body = std::str::from_utf8(built.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(
+            body,
+            "grant_type=client_credentials&nfInstanceId=8b5e2f3c-0000-4000-8000-000000000001\
+             &nfType=SCP&targetNfType=UDM&scope=nudm-sdm"
+        );
+    }
+
+    #[test]
+    fn token_endpoint_follows_the_nrf_unless_overridden() {
+        let derived = AccessTokenProvider::new(
+            Client::new(),
+            None,
+            "8b5e2f3c-0000-4000-8000-000000000001".to_string(),
+            Duration::from_secs(60),
+            Metrics::new(),
+        );
+        assert_eq!(derived.token_endpoint("http://nrf-a.example:8000"), "http://nrf-a.example:8000/oauth2/token");
+        assert_eq!(derived.token_endpoint("https://nrf-b.example/"), "https://nrf-b.example/oauth2/token");
+
+        let overridden = AccessTokenProvider::new(
+            Client::new(),
+            Some("https://auth.example/token".to_string()),
+            "8b5e2f3c-0000-4000-8000-000000000001".to_string(),
+            Duration::from_secs(60),
+            Metrics::new(),
+        );
+        assert_eq!(overridden.token_endpoint("http://nrf-a.example:8000"), "https://auth.example/token");
+    }
+}
@@ -1,47 +1,98 @@
+use std::collections::HashMap;
 use std::env;
-use crate::types::RetryConfig;
+use crate::types::{
+    AdmissionConfig, CircuitBreakerConfig, CircuitBreakerSettings, HealthCheckConfig, HealthCheckSettings, Ipv4AddressRange,
+    JitterMode, NextHopScpConfig, NonIdempotentRetryRule, NrfShutdownAction, OutlierDetectionConfig, PlmnId, RateLimitConfig,
+    RateLimitRule, ReadinessConfig, RetryBudgetConfig, RetryConfig, ScpProfileConfig, ScpRoutingConfig, ShutdownConfig,
+    StateStoreBackend, StateStoreConfig, StatusActionRule, StickySessionConfig, DEFAULT_SCP_CAPABILITY,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct OAuth2Config {
     pub enabled: bool,
     pub issuer: String,
     pub audience: Vec<String>,
+    #[allow(dead_code)]
     pub required_scope: Option<String>,
     pub secret_key: String,
 }
 
-impl Default for OAuth2Config {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            issuer: String::new(),
-            audience: Vec::new(),
-            required_scope: None,
-            secret_key: String::new(),
-        }
-    }
+#[derive(Debug, Clone)]
+pub struct NrfEndpointConfig {
+    pub uri: String,
+    pub priority: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct NrfConfig {
+    pub endpoints: Vec<NrfEndpointConfig>,
+    pub unhealthy_threshold: u32,
+    pub unhealthy_seconds: u64,
+    pub max_redirects: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OAuth2ClientConfig {
+    pub enabled: bool,
+    pub token_endpoint: Option<String>,
+    pub refresh_margin_seconds: u64,
 }
 
 #[derive(Debug, Clone)]
 pub struct TlsConfig {
     pub enabled: bool,
     pub cert_path: Option<String>,
+    #[allow(dead_code)]
     pub key_path: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct AdminConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub api_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallbackConfig {
+    pub nf_type_mappings: HashMap<String, String>,
+    pub allowed_api_roots: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub host: String,
     pub port: u16,
+    #[cfg(feature = "mongodb")]
     pub mongodb_uri: String,
-    pub nrf_uri: Option<String>,
+    #[cfg(feature = "mongodb")]
+    pub mongodb_timeout_ms: u64,
+    pub nrf: NrfConfig,
     pub nf_instance_id: String,
-    pub scp_host: String,
+    pub scp_profile: ScpProfileConfig,
+    pub scp_routing: ScpRoutingConfig,
     pub oauth2: OAuth2Config,
+    pub oauth2_client: OAuth2ClientConfig,
+    #[allow(dead_code)]
     pub tls: TlsConfig,
+    pub admin: AdminConfig,
+    #[allow(dead_code)]
     pub cache_ttl_seconds: u64,
     pub heartbeat_interval_seconds: u64,
+    pub default_max_rsp_time_ms: u64,
     pub retry: RetryConfig,
+    pub retry_budget: RetryBudgetConfig,
+    pub circuit_breaker: CircuitBreakerSettings,
+    pub health_check: HealthCheckSettings,
+    pub outlier_detection: OutlierDetectionConfig,
+    pub sticky_session: StickySessionConfig,
+    pub state_store: StateStoreConfig,
+    pub callback: CallbackConfig,
+    pub shutdown: ShutdownConfig,
+    pub readiness: ReadinessConfig,
+    pub admission: AdmissionConfig,
+    pub rate_limit: RateLimitConfig,
 }
 
 impl Config {
@@ -53,17 +104,39 @@ impl Config {
             .unwrap_or_else(|_| "7777".to_string())
             .parse()?;
 
+        #[cfg(feature = "mongodb")]
         let mongodb_uri = env::var("MONGODB_URI")
             .unwrap_or_else(|_| "mongodb://localhost:27017".to_string());
 
-        let nrf_uri = env::var("NRF_URI").ok();
+        #[cfg(feature = "mongodb")]
+        let mongodb_timeout_ms = env::var("MONGODB_TIMEOUT_MS")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse()?;
+
+        let nrf_endpoints = load_nrf_endpoints();
+
+        let nrf_unhealthy_threshold = env::var("NRF_UNHEALTHY_THRESHOLD")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?;
+
+        let nrf_unhealthy_seconds = env::var("NRF_UNHEALTHY_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()?;
+
+        let nrf_max_redirects = env::var("NRF_MAX_REDIRECTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?;
+
+        let nrf = NrfConfig {
+            endpoints: nrf_endpoints,
+            unhealthy_threshold: nrf_unhealthy_threshold,
+            unhealthy_seconds: nrf_unhealthy_seconds,
+            max_redirects: nrf_max_redirects,
+        };
 
         let nf_instance_id = env::var("NF_INSTANCE_ID")
             .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
 
-        let scp_host = env::var("SCP_ADVERTISED_HOST")
-            .unwrap_or_else(|_| "127.0.0.1".to_string());
-
         let cache_ttl_seconds = env::var("CACHE_TTL_SECONDS")
             .unwrap_or_else(|_| "300".to_string())
             .parse()?;
@@ -72,6 +145,10 @@ impl Config {
             .unwrap_or_else(|_| "30".to_string())
             .parse()?;
 
+        let default_max_rsp_time_ms = env::var("DEFAULT_MAX_RSP_TIME_MS")
+            .unwrap_or_else(|_| "10000".to_string())
+            .parse()?;
+
         let oauth2_enabled = env::var("OAUTH2_ENABLED")
             .unwrap_or_else(|_| "false".to_string())
             .parse()
@@ -100,6 +177,25 @@ impl Config {
             secret_key: jwt_secret,
         };
 
+        let oauth2_client_enabled = env::var("NRF_OAUTH2_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let oauth2_client_token_endpoint = env::var("NRF_OAUTH2_TOKEN_ENDPOINT")
+            .ok()
+            .filter(|value| !value.is_empty());
+
+        let oauth2_client_refresh_margin_seconds = env::var("NRF_OAUTH2_REFRESH_MARGIN_SECONDS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()?;
+
+        let oauth2_client = OAuth2ClientConfig {
+            enabled: oauth2_client_enabled,
+            token_endpoint: oauth2_client_token_endpoint,
+            refresh_margin_seconds: oauth2_client_refresh_margin_seconds,
+        };
+
         let tls_enabled = env::var("TLS_ENABLED")
             .unwrap_or_else(|_| "false".to_string())
             .parse()
@@ -114,6 +210,33 @@ impl Config {
             key_path: tls_key_path,
         };
 
+        let scp_profile = load_scp_profile_config(port, tls.enabled)?;
+
+        let scp_routing = load_scp_routing_config()?;
+
+        let admin_enabled = env::var("ADMIN_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let admin_host = env::var("ADMIN_HOST")
+            .unwrap_or_else(|_| "127.0.0.1".to_string());
+
+        let admin_port = env::var("ADMIN_PORT")
+            .unwrap_or_else(|_| "7778".to_string())
+            .parse()?;
+
+        let admin_api_token = env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty());
+
+        let admin = AdminConfig {
+            enabled: admin_enabled,
+            host: admin_host,
+            port: admin_port,
+            api_token: admin_api_token,
+        };
+
         let retry_max_attempts = env::var("RETRY_MAX_ATTEMPTS")
             .unwrap_or_else(|_| "3".to_string())
             .parse()
@@ -134,25 +257,597 @@ impl Config {
             .parse()
             .unwrap_or(2.0);
 
+        let retry_jitter = env::var("RETRY_JITTER")
+            .ok()
+            .and_then(|value| JitterMode::parse(&value))
+            .unwrap_or(JitterMode::Full);
+
+        let retry_non_idempotent_rules = env::var("RETRY_NON_IDEMPOTENT_RULES")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .filter_map(|rule| {
+                let parsed = NonIdempotentRetryRule::parse(rule);
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring invalid RETRY_NON_IDEMPOTENT_RULES entry: {}", rule);
+                }
+                parsed
+            })
+            .collect();
+
+        let retry_status_actions = env::var("RETRY_STATUS_ACTIONS")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .filter_map(|rule| {
+                let parsed = StatusActionRule::parse(rule);
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring invalid RETRY_STATUS_ACTIONS entry: {}", rule);
+                }
+                parsed
+            })
+            .collect();
+
         let retry = RetryConfig {
             max_attempts: retry_max_attempts,
             initial_backoff_ms: retry_initial_backoff_ms,
             max_backoff_ms: retry_max_backoff_ms,
             backoff_multiplier: retry_backoff_multiplier,
+            jitter: retry_jitter,
+            non_idempotent_rules: retry_non_idempotent_rules,
+            status_actions: retry_status_actions,
+        };
+
+        let retry_budget_enabled = env::var("RETRY_BUDGET_ENABLED")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true);
+
+        let retry_budget_ratio = env::var("RETRY_BUDGET_RATIO")
+            .unwrap_or_else(|_| "0.2".to_string())
+            .parse()
+            .unwrap_or(0.2);
+
+        let retry_budget_max_tokens = env::var("RETRY_BUDGET_MAX_TOKENS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10.0);
+
+        let retry_budget = RetryBudgetConfig {
+            enabled: retry_budget_enabled,
+            ratio: retry_budget_ratio,
+            max_tokens: retry_budget_max_tokens,
+        };
+
+        let circuit_breaker = load_circuit_breaker_settings();
+
+        let health_check = load_health_check_settings()?;
+
+        let outlier_detection = load_outlier_detection_config()?;
+
+        let sticky_session_ttl_seconds = env::var("STICKY_SESSION_TTL_SECONDS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()?;
+
+        let sticky_session_max_entries = env::var("STICKY_SESSION_MAX_ENTRIES")
+            .unwrap_or_else(|_| "100000".to_string())
+            .parse()?;
+
+        let sticky_session_sweep_interval_seconds = env::var("STICKY_SESSION_SWEEP_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()?;
+
+        let sticky_session = StickySessionConfig {
+            ttl_seconds: sticky_session_ttl_seconds,
+            max_entries: sticky_session_max_entries,
+            sweep_interval_seconds: sticky_session_sweep_interval_seconds,
+        };
+
+        let state_store_backend = env::var("STATE_STORE_BACKEND")
+            .ok()
+            .and_then(|value| StateStoreBackend::parse(&value))
+            .unwrap_or(StateStoreBackend::Memory);
+
+        let state_store_binding_ttl_seconds = env::var("BINDING_TTL_SECONDS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()?;
+
+        let state_store_write_interval_seconds = env::var("STATE_STORE_WRITE_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()?;
+
+        let state_store_negative_cache_seconds = env::var("BINDING_NEGATIVE_CACHE_SECONDS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()?;
+
+        let state_store = StateStoreConfig {
+            backend: state_store_backend,
+            binding_ttl_seconds: state_store_binding_ttl_seconds,
+            write_interval_seconds: state_store_write_interval_seconds,
+            negative_cache_seconds: state_store_negative_cache_seconds,
+        };
+
+        let callback_nf_type_mappings = env::var("CALLBACK_NF_TYPE_MAPPINGS")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .filter_map(|entry| {
+                let (callback_type, nf_type) = entry.split_once('=')?;
+                let callback_type = callback_type.trim();
+                let nf_type = nf_type.trim();
+                if callback_type.is_empty() || nf_type.is_empty() {
+                    return None;
+                }
+                Some((callback_type.to_string(), nf_type.to_string()))
+            })
+            .collect();
+
+        let callback_allowed_api_roots = env::var("CALLBACK_ALLOWED_API_ROOTS")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| url::Url::parse(s).map(|url| url.origin().ascii_serialization()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let callback = CallbackConfig {
+            nf_type_mappings: callback_nf_type_mappings,
+            allowed_api_roots: callback_allowed_api_roots,
+        };
+
+        let shutdown_nrf_action = env::var("SHUTDOWN_NRF_ACTION")
+            .ok()
+            .and_then(|value| NrfShutdownAction::parse(&value))
+            .unwrap_or(NrfShutdownAction::Deregister);
+
+        let shutdown_grace_period_seconds = env::var("SHUTDOWN_GRACE_PERIOD_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()?;
+
+        let shutdown = ShutdownConfig {
+            nrf_action: shutdown_nrf_action,
+            grace_period_seconds: shutdown_grace_period_seconds,
+        };
+
+        let readiness_max_missed_heartbeats = env::var("READINESS_MAX_MISSED_HEARTBEATS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?;
+
+        let readiness = ReadinessConfig {
+            critical_nf_types: env_list("READINESS_CRITICAL_NF_TYPES")
+                .into_iter()
+                .map(|nf_type| nf_type.to_uppercase())
+                .collect(),
+            max_missed_heartbeats: readiness_max_missed_heartbeats,
+            tls_cert_path: tls.cert_path.clone().filter(|_| tls.enabled),
+        };
+
+        let admission_max_in_flight = env::var("ADMISSION_MAX_IN_FLIGHT")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()?;
+
+        let admission_default_nf_type_limit = env::var("ADMISSION_DEFAULT_NF_TYPE_LIMIT")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()?;
+
+        let admission_nf_type_limits = env::var("ADMISSION_NF_TYPE_LIMITS")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .filter_map(|entry| {
+                let parsed = entry
+                    .split_once('=')
+                    .and_then(|(nf_type, limit)| Some((nf_type.trim().to_uppercase(), limit.trim().parse().ok()?)));
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring invalid ADMISSION_NF_TYPE_LIMITS entry: {}", entry);
+                }
+                parsed
+            })
+            .collect();
+
+        let admission_shed_threshold_percent = env::var("ADMISSION_SHED_THRESHOLD_PERCENT")
+            .unwrap_or_else(|_| "80".to_string())
+            .parse()?;
+
+        let admission_retry_after_seconds = env::var("ADMISSION_RETRY_AFTER_SECONDS")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()?;
+
+        let admission_oci_validity_seconds = env::var("ADMISSION_OCI_VALIDITY_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()?;
+
+        let admission = AdmissionConfig {
+            max_in_flight: admission_max_in_flight,
+            default_nf_type_limit: admission_default_nf_type_limit,
+            nf_type_limits: admission_nf_type_limits,
+            shed_threshold_percent: admission_shed_threshold_percent,
+            retry_after_seconds: admission_retry_after_seconds,
+            overload_validity_seconds: admission_oci_validity_seconds,
+        };
+
+        let rate_limit_enabled = env::var("RATE_LIMIT_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let rate_limit_rules = env::var("RATE_LIMIT_RULES")
+            .unwrap_or_else(|_| "".to_string())
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .filter_map(|rule| {
+                let parsed = RateLimitRule::parse(rule);
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring invalid RATE_LIMIT_RULES entry: {}", rule);
+                }
+                parsed
+            })
+            .collect();
+
+        let rate_limit_reload_interval_seconds = env::var("RATE_LIMIT_RELOAD_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()?;
+
+        let rate_limit_shared = env::var("RATE_LIMIT_SHARED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let rate_limit_shared_timeout_ms = env::var("RATE_LIMIT_SHARED_TIMEOUT_MS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()?;
+
+        let rate_limit = RateLimitConfig {
+            enabled: rate_limit_enabled,
+            rules: rate_limit_rules,
+            rules_file: env::var("RATE_LIMIT_RULES_FILE").ok().filter(|value| !value.is_empty()),
+            reload_interval_seconds: rate_limit_reload_interval_seconds,
+            shared: rate_limit_shared,
+            shared_timeout_ms: rate_limit_shared_timeout_ms,
+            peer_identity_header: env::var("RATE_LIMIT_PEER_IDENTITY_HEADER")
+                .ok()
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_lowercase()),
         };
 
         Ok(Self {
             host,
             port,
+            #[cfg(feature = "mongodb")]
             mongodb_uri,
-            nrf_uri,
+            #[cfg(feature = "mongodb")]
+            mongodb_timeout_ms,
+            nrf,
             nf_instance_id,
-            scp_host,
+            scp_profile,
+            scp_routing,
             oauth2,
+            oauth2_client,
             tls,
+            admin,
             cache_ttl_seconds,
             heartbeat_interval_seconds,
+            default_max_rsp_time_ms,
             retry,
+            retry_budget,
+            circuit_breaker,
+            health_check,
+            outlier_detection,
+            sticky_session,
+            state_store,
+            callback,
+            shutdown,
+            readiness,
+            admission,
+            rate_limit,
+        })
+    }
+}
+
+fn load_nrf_endpoints() -> Vec<NrfEndpointConfig> {
+    let value = env::var("NRF_URIS")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| env::var("NRF_URI").ok())
+        .unwrap_or_default();
+
+    let mut endpoints: Vec<NrfEndpointConfig> = value
+        .split(',')
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let mut parts = entry.split(';');
+            let uri = parts.next()?.trim().trim_end_matches('/');
+            if uri.is_empty() {
+                return None;
+            }
+
+            let priority = parts
+                .filter_map(|parameter| parameter.split_once('='))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case("priority"))
+                .and_then(|(_, value)| value.trim().parse().ok())
+                .unwrap_or(index as u32);
+
+            Some(NrfEndpointConfig {
+                uri: uri.to_string(),
+                priority,
+            })
+        })
+        .collect();
+
+    endpoints.sort_by_key(|endpoint| endpoint.priority);
+    endpoints
+}
+
+fn load_scp_profile_config(listen_port: u16, tls_enabled: bool) -> anyhow::Result<ScpProfileConfig> {
+    let fqdn = env::var("SCP_FQDN")
+        .ok()
+        .filter(|value| !value.is_empty());
+
+    let ipv4_addresses = env_list("SCP_ADVERTISED_HOST");
+    let ipv4_addresses = if ipv4_addresses.is_empty() {
+        vec!["127.0.0.1".to_string()]
+    } else {
+        ipv4_addresses
+    };
+
+    let port = match env::var("SCP_ADVERTISED_PORT").ok().filter(|value| !value.is_empty()) {
+        Some(value) => value.parse()?,
+        None => listen_port,
+    };
+
+    let capacity = env::var("SCP_CAPACITY")
+        .unwrap_or_else(|_| "100".to_string())
+        .parse()?;
+
+    let priority = env::var("SCP_PRIORITY")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()?;
+
+    let locality = env::var("SCP_LOCALITY")
+        .ok()
+        .filter(|value| !value.is_empty());
+
+    let scp_prefix = env::var("SCP_PREFIX")
+        .ok()
+        .filter(|value| !value.is_empty());
+
+    let ipv4_addr_ranges = env_list("SCP_IPV4_ADDR_RANGES")
+        .into_iter()
+        .filter_map(|range| {
+            let parsed = Ipv4AddressRange::parse(&range);
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid SCP_IPV4_ADDR_RANGES entry: {}", range);
+            }
+            parsed
+        })
+        .collect();
+
+    let capabilities = env::var("SCP_CAPABILITIES")
+        .unwrap_or_else(|_| DEFAULT_SCP_CAPABILITY.to_string())
+        .split(',')
+        .map(|capability| capability.trim().to_uppercase())
+        .filter(|capability| !capability.is_empty())
+        .collect();
+
+    Ok(ScpProfileConfig {
+        fqdn,
+        ipv4_addresses,
+        scheme: if tls_enabled { "https" } else { "http" }.to_string(),
+        port,
+        capacity,
+        priority,
+        locality,
+        plmn_list: load_plmn_list("SCP_PLMN_LIST"),
+        nf_set_ids: env_list("SCP_NF_SET_IDS"),
+        scp_domains: env_list("SCP_DOMAINS"),
+        scp_prefix,
+        served_nf_set_ids: env_list("SCP_SERVED_NF_SET_IDS"),
+        address_domains: env_list("SCP_ADDRESS_DOMAINS"),
+        ipv4_addr_ranges,
+        remote_plmns: load_plmn_list("SCP_REMOTE_PLMN_LIST"),
+        capabilities,
+    })
+}
+
+fn load_scp_routing_config() -> anyhow::Result<ScpRoutingConfig> {
+    let next_hops = env::var("SCP_NEXT_HOPS")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let parsed = NextHopScpConfig::parse(entry, index as u32);
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid SCP_NEXT_HOPS entry: {}", entry);
+            }
+            parsed
+        })
+        .collect();
+
+    let nrf_discovery = env::var("SCP_NEXT_HOP_DISCOVERY")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .unwrap_or(false);
+
+    let discovery_interval_seconds = env::var("SCP_NEXT_HOP_DISCOVERY_INTERVAL_SECONDS")
+        .unwrap_or_else(|_| "60".to_string())
+        .parse()?;
+
+    let unhealthy_threshold = env::var("SCP_NEXT_HOP_UNHEALTHY_THRESHOLD")
+        .unwrap_or_else(|_| "3".to_string())
+        .parse()?;
+
+    let unhealthy_seconds = env::var("SCP_NEXT_HOP_UNHEALTHY_SECONDS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()?;
+
+    let max_hops = env::var("SCP_MAX_HOPS")
+        .unwrap_or_else(|_| "8".to_string())
+        .parse()?;
+
+    Ok(ScpRoutingConfig {
+        next_hops,
+        nrf_discovery,
+        discovery_interval_seconds,
+        unhealthy_threshold,
+        unhealthy_seconds,
+        max_hops,
+    })
+}
+
+fn load_plmn_list(key: &str) -> Vec<PlmnId> {
+    env_list(key)
+        .into_iter()
+        .filter_map(|plmn| {
+            let parsed = PlmnId::parse(&plmn);
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid {} entry: {} (expected MCC-MNC)", key, plmn);
+            }
+            parsed
+        })
+        .collect()
+}
+
+fn env_list(key: &str) -> Vec<String> {
+    env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+struct NfTypeSetting {
+    nf_type: Option<String>,
+    param: &'static str,
+    key: String,
+    value: String,
+}
+
+fn scan_nf_type_settings(prefix: &str, params: &[&'static str]) -> Vec<NfTypeSetting> {
+    let mut settings: Vec<NfTypeSetting> = env::vars()
+        .filter_map(|(key, value)| {
+            let rest = key.strip_prefix(prefix)?;
+
+            if let Some(param) = params.iter().find(|param| **param == rest) {
+                return Some(NfTypeSetting { nf_type: None, param, key, value });
+            }
+
+            let param = params
+                .iter()
+                .find(|param| rest.ends_with(&format!("_{}", param)))?;
+            let nf_type = rest[..rest.len() - param.len() - 1].to_string();
+
+            Some(NfTypeSetting { nf_type: Some(nf_type), param, key, value })
         })
+        .collect();
+
+    settings.sort_by_key(|setting| setting.nf_type.is_some());
+    settings
+}
+
+const CIRCUIT_BREAKER_PARAMS: &[&str] = &[
+    "FAILURE_RATE_THRESHOLD",
+    "MINIMUM_REQUESTS",
+    "WINDOW_SECONDS",
+    "OPEN_SECONDS",
+    "HALF_OPEN_MAX_REQUESTS",
+];
+
+fn load_circuit_breaker_settings() -> CircuitBreakerSettings {
+    let mut settings = CircuitBreakerSettings::default();
+
+    for setting in scan_nf_type_settings("CIRCUIT_BREAKER_", CIRCUIT_BREAKER_PARAMS) {
+        let config: &mut CircuitBreakerConfig = match setting.nf_type {
+            Some(nf_type) => {
+                let default = settings.default.clone();
+                settings.nf_types.entry(nf_type).or_insert(default)
+            }
+            None => &mut settings.default,
+        };
+
+        if !config.apply(setting.param, &setting.value) {
+            tracing::warn!("Ignoring invalid value for {}: {}", setting.key, setting.value);
+        }
     }
+
+    settings
+}
+
+const HEALTH_CHECK_PARAMS: &[&str] = &[
+    "TYPE",
+    "HTTP_PATH",
+    "TIMEOUT_MS",
+    "HEALTHY_THRESHOLD",
+    "UNHEALTHY_THRESHOLD",
+];
+
+fn load_health_check_settings() -> anyhow::Result<HealthCheckSettings> {
+    let enabled = env::var("HEALTH_CHECK_ENABLED")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .unwrap_or(false);
+
+    let interval_seconds = env::var("HEALTH_CHECK_INTERVAL_SECONDS")
+        .unwrap_or_else(|_| "10".to_string())
+        .parse()?;
+
+    let mut settings = HealthCheckSettings {
+        enabled,
+        interval_seconds,
+        ..Default::default()
+    };
+
+    for setting in scan_nf_type_settings("HEALTH_CHECK_", HEALTH_CHECK_PARAMS) {
+        let config: &mut HealthCheckConfig = match setting.nf_type {
+            Some(nf_type) => {
+                let default = settings.default.clone();
+                settings.nf_types.entry(nf_type).or_insert(default)
+            }
+            None => &mut settings.default,
+        };
+
+        if !config.apply(setting.param, &setting.value) {
+            tracing::warn!("Ignoring invalid value for {}: {}", setting.key, setting.value);
+        }
+    }
+
+    Ok(settings)
+}
+
+fn load_outlier_detection_config() -> anyhow::Result<OutlierDetectionConfig> {
+    Ok(OutlierDetectionConfig {
+        enabled: env::var("OUTLIER_DETECTION_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()?,
+        interval_seconds: env::var("OUTLIER_DETECTION_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()?,
+        base_ejection_seconds: env::var("OUTLIER_DETECTION_BASE_EJECTION_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()?,
+        max_ejection_seconds: env::var("OUTLIER_DETECTION_MAX_EJECTION_SECONDS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()?,
+        max_ejection_percent: env::var("OUTLIER_DETECTION_MAX_EJECTION_PERCENT")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()?,
+        success_rate_minimum_hosts: env::var("OUTLIER_DETECTION_SUCCESS_RATE_MINIMUM_HOSTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?,
+        success_rate_request_volume: env::var("OUTLIER_DETECTION_SUCCESS_RATE_REQUEST_VOLUME")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()?,
+        success_rate_stdev_factor: env::var("OUTLIER_DETECTION_SUCCESS_RATE_STDEV_FACTOR")
+            .unwrap_or_else(|_| "1.9".to_string())
+            .parse()?,
+        latency_minimum_hosts: env::var("OUTLIER_DETECTION_LATENCY_MINIMUM_HOSTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?,
+        latency_request_volume: env::var("OUTLIER_DETECTION_LATENCY_REQUEST_VOLUME")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()?,
+        latency_p99_factor: env::var("OUTLIER_DETECTION_LATENCY_P99_FACTOR")
+            .unwrap_or_else(|_| "3.0".to_string())
+            .parse()?,
+    })
 }
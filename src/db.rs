@@ -1,48 +1,222 @@
-use mongodb::{Client, Database};
 use std::sync::Arc;
-use dashmap::DashMap;
+use crate::clients::oauth2::AccessTokenProvider;
 use crate::config::Config;
-use crate::types::{AppState, CachedNfProfile};
+use crate::types::AppState;
+use crate::services::admission::AdmissionController;
+use crate::services::callback_router::CallbackRouter;
+use crate::services::lifecycle::Lifecycle;
 use crate::services::load_balancer::LoadBalancer;
+use crate::services::metrics::Metrics;
+use crate::services::nrf_registration::NrfRegistration;
+use crate::services::outlier_detection::OutlierDetector;
+use crate::services::rate_limiter::RateLimiter;
+use crate::services::retry_budget::RetryBudget;
+use crate::services::scp_router::ScpRouter;
+use crate::services::discovery_cache::DiscoveryCache;
+use crate::services::session_store::SessionStore;
+use crate::store::{MemoryStateStore, StateStore};
+use crate::types::StateStoreBackend;
 
 pub async fn init(config: &Config) -> anyhow::Result<AppState> {
-    let client = Client::with_uri_str(&config.mongodb_uri).await?;
-    let db = client.database("scp");
-
-    tracing::info!("Connected to MongoDB");
-
-    init_collections(&db).await?;
+    let state_store = init_state_store(config).await;
 
     let http_client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
+        .redirect(reqwest::redirect::Policy::none())
         .build()?;
 
-    let nrf_client = if let Some(nrf_uri) = &config.nrf_uri {
-        Some(Arc::new(
-            crate::clients::nrf::NrfClient::new(nrf_uri.clone(), http_client.clone())
-        ))
+    let nf_instance_id = uuid::Uuid::parse_str(&config.nf_instance_id)?;
+
+    let metrics = Metrics::new();
+
+    let access_tokens = init_access_token_provider(config, &http_client, &nf_instance_id, &metrics);
+
+    let nrf_client = if !config.nrf.endpoints.is_empty() {
+        let nrf_http_client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        let mut nrf_client = crate::clients::nrf::NrfClient::new(&config.nrf, nrf_http_client);
+        if let Some(access_tokens) = &access_tokens {
+            nrf_client = nrf_client.with_token_provider(access_tokens.clone());
+        }
+        Some(Arc::new(nrf_client))
     } else {
         tracing::warn!("NRF URI not configured, service discovery will be unavailable");
         None
     };
 
-    let nf_profile_cache = Arc::new(DashMap::new());
+    let nrf_registration = nrf_client.clone().map(|nrf_client| {
+        NrfRegistration::new(
+            nrf_client,
+            config
+                .scp_profile
+                .to_nf_profile(&nf_instance_id.to_string(), config.heartbeat_interval_seconds as u32),
+            config.heartbeat_interval_seconds,
+        )
+    });
 
-    let nf_instance_id = uuid::Uuid::parse_str(&config.nf_instance_id)?;
+    let nf_profile_cache = DiscoveryCache::new(state_store.clone());
+    nf_profile_cache.warm().await;
+
+    let load_balancer = LoadBalancer::new(
+        config.circuit_breaker.clone(),
+        config.sticky_session.clone(),
+        state_store.clone(),
+        metrics.clone(),
+    );
+
+    if state_store.is_shared() {
+        match state_store.list_circuit_states().await {
+            Ok(states) => {
+                let restored = load_balancer.restore_circuit_states(states);
+                if restored > 0 {
+                    tracing::info!("Restored {} open circuits from state store", restored);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load circuit states from state store: {}", e),
+        }
+    }
+
+    let session_store = SessionStore::new(
+        state_store.clone(),
+        config.state_store.clone(),
+        config.sticky_session.ttl(),
+        metrics.clone(),
+    );
+
+    let outlier_detector = OutlierDetector::new(
+        config.outlier_detection.clone(),
+        load_balancer.clone(),
+        metrics.clone(),
+    );
+
+    let retry_budget = RetryBudget::new(config.retry_budget.clone(), metrics.clone());
+
+    let callback_router = CallbackRouter::new(&config.callback);
+
+    let local_host = config
+        .scp_profile
+        .fqdn
+        .clone()
+        .or_else(|| config.scp_profile.ipv4_addresses.first().cloned())
+        .unwrap_or_else(|| config.host.clone());
+    let scp_router = ScpRouter::new(
+        config.scp_routing.clone(),
+        &local_host,
+        nf_instance_id.to_string(),
+        &config.scp_profile.scp_domains,
+        metrics.clone(),
+    );
+
+    let admission_identity = match &config.scp_profile.fqdn {
+        Some(fqdn) => format!("SCP-FQDN: {}", fqdn),
+        None => format!("NF-Inst: {}", nf_instance_id),
+    };
+    let admission = AdmissionController::new(config.admission.clone(), admission_identity, metrics.clone());
 
-    let load_balancer = LoadBalancer::new();
+    let rate_limiter = RateLimiter::new(
+        config.rate_limit.clone(),
+        &config.oauth2,
+        state_store.clone(),
+        metrics.clone(),
+    );
 
     Ok(AppState {
         nf_instance_id,
         nrf_client,
+        nrf_registration,
         http_client,
         nf_profile_cache,
         load_balancer,
+        session_store,
+        state_store,
+        outlier_detector,
         retry_config: config.retry.clone(),
+        retry_budget,
+        metrics,
+        default_max_rsp_time: std::time::Duration::from_millis(config.default_max_rsp_time_ms),
+        callback_router,
+        scp_router,
+        lifecycle: Lifecycle::new(),
+        admission,
+        rate_limiter,
+        readiness: config.readiness.clone(),
         start_time: std::time::Instant::now(),
     })
 }
 
-async fn init_collections(_db: &Database) -> anyhow::Result<()> {
-    Ok(())
+fn init_access_token_provider(
+    config: &Config,
+    http_client: &reqwest::Client,
+    nf_instance_id: &uuid::Uuid,
+    metrics: &Metrics,
+) -> Option<AccessTokenProvider> {
+    if !config.oauth2_client.enabled {
+        return None;
+    }
+
+    let token_endpoint = config.oauth2_client.token_endpoint.clone();
+
+    match &token_endpoint {
+        Some(token_endpoint) => tracing::info!("Requesting OAuth2 access tokens from {}", token_endpoint),
+        None => tracing::info!("Requesting OAuth2 access tokens from each NRF's /oauth2/token endpoint"),
+    }
+
+    Some(AccessTokenProvider::new(
+        http_client.clone(),
+        token_endpoint,
+        nf_instance_id.to_string(),
+        std::time::Duration::from_secs(config.oauth2_client.refresh_margin_seconds),
+        metrics.clone(),
+    ))
+}
+
+async fn init_state_store(config: &Config) -> Arc<dyn StateStore> {
+    match config.state_store.backend {
+        StateStoreBackend::Memory => {
+            tracing::info!("Using in-memory state store");
+            Arc::new(MemoryStateStore::new())
+        }
+        #[cfg(feature = "mongodb")]
+        StateStoreBackend::MongoDb => match connect_mongodb(config).await {
+            Ok(store) => store,
+            Err(e) => {
+                tracing::error!("Failed to set up MongoDB state store, falling back to in-memory state: {}", e);
+                Arc::new(MemoryStateStore::new())
+            }
+        },
+        #[cfg(not(feature = "mongodb"))]
+        StateStoreBackend::MongoDb => {
+            tracing::warn!("STATE_STORE_BACKEND=mongodb but the SCP was built without the mongodb feature, using in-memory state store");
+            Arc::new(MemoryStateStore::new())
+        }
+    }
+}
+
+#[cfg(feature = "mongodb")]
+async fn connect_mongodb(config: &Config) -> anyhow::Result<Arc<dyn StateStore>> {
+    use crate::store::MongoStateStore;
+    use mongodb::options::ClientOptions;
+    use mongodb::Client;
+    use std::time::Duration;
+
+    let timeout = Duration::from_millis(config.mongodb_timeout_ms);
+
+    let mut options = ClientOptions::parse(&config.mongodb_uri).await?;
+    options.server_selection_timeout = Some(timeout);
+    options.connect_timeout = Some(timeout);
+
+    let client = Client::with_options(options)?;
+    let store = MongoStateStore::new(&client.database("scp"));
+
+    if store.check_connectivity().await {
+        tracing::info!("Using MongoDB state store for sessions, bindings, circuit state and discovery cache");
+    } else {
+        tracing::warn!("MongoDB is unreachable, persistence is degraded until it becomes available");
+    }
+
+    tokio::spawn(store.clone().run_monitor(Duration::from_secs(5)));
+
+    Ok(Arc::new(store))
 }
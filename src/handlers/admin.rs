@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use crate::services::load_balancer::{AdminState, InstanceSnapshot, StickySessionSnapshot};
+use crate::types::{AppError, AppState, NfProfile};
+
+pub async fn list_instances(State(state): State<AppState>) -> Json<Vec<InstanceSnapshot>> {
+    let known_instances: Vec<NfProfile> = state.nf_profile_cache.profiles();
+
+    Json(state.load_balancer.instance_snapshots(&known_instances))
+}
+
+pub async fn drain_instance(
+    State(state): State<AppState>,
+    Path(nf_instance_id): Path<String>,
+) -> StatusCode {
+    state.load_balancer.set_admin_state(&nf_instance_id, AdminState::Draining);
+    StatusCode::NO_CONTENT
+}
+
+pub async fn disable_instance(
+    State(state): State<AppState>,
+    Path(nf_instance_id): Path<String>,
+) -> StatusCode {
+    state.load_balancer.set_admin_state(&nf_instance_id, AdminState::Disabled);
+    StatusCode::NO_CONTENT
+}
+
+pub async fn enable_instance(
+    State(state): State<AppState>,
+    Path(nf_instance_id): Path<String>,
+) -> StatusCode {
+    state.load_balancer.set_admin_state(&nf_instance_id, AdminState::Active);
+    StatusCode::NO_CONTENT
+}
+
+pub async fn reset_circuit(
+    State(state): State<AppState>,
+    Path(nf_instance_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    if state.load_balancer.reset_circuit(&nf_instance_id) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound(format!("No circuit state for NF instance {}", nf_instance_id)))
+    }
+}
+
+pub async fn list_sticky_sessions(State(state): State<AppState>) -> Json<Vec<StickySessionSnapshot>> {
+    Json(state.load_balancer.sticky_session_snapshots())
+}
+
+pub async fn delete_sticky_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let removed = state.load_balancer.remove_sticky_session(&session_id);
+    state.session_store.remove_sticky_session(&session_id);
+
+    if removed || state.session_store.is_shared() {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound(format!("No sticky session {}", session_id)))
+    }
+}
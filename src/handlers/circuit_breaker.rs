@@ -0,0 +1,54 @@
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use crate::services::load_balancer::{CircuitSnapshot, CircuitTransitionRecord};
+use crate::types::{AppError, AppState, CircuitBreakerConfig, CircuitBreakerSettings};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerOverview {
+    pub settings: CircuitBreakerSettings,
+    pub instances: Vec<CircuitSnapshot>,
+    pub transitions: Vec<CircuitTransitionRecord>,
+}
+
+pub async fn list_circuit_breakers(State(state): State<AppState>) -> Json<CircuitBreakerOverview> {
+    Json(CircuitBreakerOverview {
+        settings: state.load_balancer.circuit_settings(),
+        instances: state.load_balancer.circuit_snapshots(),
+        transitions: state.load_balancer.circuit_transitions(),
+    })
+}
+
+pub async fn update_circuit_breaker_config(
+    State(state): State<AppState>,
+    Path(nf_type): Path<String>,
+    payload: Result<Json<CircuitBreakerConfig>, JsonRejection>,
+) -> Result<(StatusCode, Json<CircuitBreakerSettings>), AppError> {
+    let Json(config) = payload?;
+    if !(0.0..=1.0).contains(&config.failure_rate_threshold) {
+        return Err(AppError::BadRequest(
+            "failureRateThreshold must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if config.half_open_max_requests == 0 {
+        return Err(AppError::BadRequest(
+            "halfOpenMaxRequests must be at least 1".to_string(),
+        ));
+    }
+
+    let target = if nf_type.eq_ignore_ascii_case("default") {
+        None
+    } else {
+        Some(nf_type.as_str())
+    };
+
+    tracing::info!("Updating circuit breaker config for {}: {:?}", nf_type, config);
+    state.load_balancer.set_circuit_config(target, config);
+
+    Ok((StatusCode::OK, Json(state.load_balancer.circuit_settings())))
+}
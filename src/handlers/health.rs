@@ -1,14 +1,25 @@
 use axum::{http::StatusCode, Json, extract::State};
 use serde::{Deserialize, Serialize};
 use crate::types::AppState;
+use crate::clients::nrf::{NfDiscoveryParams, NrfEndpointStatus};
 use crate::services::load_balancer::LoadBalancerStats;
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HealthResponse {
     pub status: String,
 }
 
-pub async fn health_check() -> (StatusCode, Json<HealthResponse>) {
+pub async fn health_check(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
+    if state.lifecycle.is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(HealthResponse {
+                status: "shutting_down".to_string(),
+            }),
+        );
+    }
+
     (
         StatusCode::OK,
         Json(HealthResponse {
@@ -17,19 +28,72 @@ pub async fn health_check() -> (StatusCode, Json<HealthResponse>) {
     )
 }
 
+pub async fn liveness() -> (StatusCode, Json<HealthResponse>) {
+    (
+        StatusCode::OK,
+        Json(HealthResponse {
+            status: "alive".to_string(),
+        }),
+    )
+}
+
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let mut checks = vec![
+        ReadinessCheck::from_result(
+            "shutdown",
+            if state.lifecycle.is_draining() {
+                Err("shutting down".to_string())
+            } else {
+                Ok(None)
+            },
+        ),
+        nrf_registration_check(&state),
+        nrf_heartbeat_check(&state),
+        database_check(&state).await,
+        tls_certificate_check(&state),
+    ];
+    checks.extend(critical_nf_type_checks(&state).await);
+
+    let ready = checks.iter().all(|check| check.status != "fail");
+
+    (
+        if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE },
+        Json(ReadinessResponse {
+            status: if ready { "ready" } else { "not_ready" }.to_string(),
+            checks,
+        }),
+    )
+}
+
 pub async fn status(State(state): State<AppState>) -> (StatusCode, Json<StatusResponse>) {
     let uptime_secs = state.start_time.elapsed().as_secs();
 
-    let nrf_status = if state.nrf_client.is_some() {
-        "connected"
-    } else {
+    let nrf_endpoints = state
+        .nrf_client
+        .as_ref()
+        .map(|nrf_client| nrf_client.endpoint_status())
+        .unwrap_or_default();
+
+    let nrf_status = if state.nrf_client.is_none() {
         "not_configured"
+    } else if !nrf_endpoints.iter().any(|endpoint| endpoint.healthy) {
+        "unavailable"
+    } else if state
+        .nrf_registration
+        .as_ref()
+        .is_some_and(|registration| !registration.is_registered())
+    {
+        "unregistered"
+    } else {
+        "connected"
     };
 
     let cached_profiles = state.nf_profile_cache.len();
 
     let lb_stats = state.load_balancer.get_statistics();
 
+    let database = database_status(&state).await;
+
     (
         StatusCode::OK,
         Json(StatusResponse {
@@ -38,6 +102,8 @@ pub async fn status(State(state): State<AppState>) -> (StatusCode, Json<StatusRe
             nf_instance_id: state.nf_instance_id.to_string(),
             uptime_seconds: uptime_secs,
             nrf_status: nrf_status.to_string(),
+            nrf_endpoints,
+            database,
             cache: CacheStats {
                 cached_nf_profiles: cached_profiles,
             },
@@ -46,6 +112,43 @@ pub async fn status(State(state): State<AppState>) -> (StatusCode, Json<StatusRe
     )
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ReadinessCheck {
+    fn from_result(name: &str, result: Result<Option<String>, String>) -> Self {
+        let (status, detail) = match result {
+            Ok(detail) => ("pass", detail),
+            Err(detail) => ("fail", Some(detail)),
+        };
+
+        Self {
+            name: name.to_string(),
+            status: status.to_string(),
+            detail,
+        }
+    }
+
+    fn skipped(name: &str, detail: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: "skip".to_string(),
+            detail: Some(detail.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatusResponse {
     pub service: String,
@@ -53,6 +156,8 @@ pub struct StatusResponse {
     pub nf_instance_id: String,
     pub uptime_seconds: u64,
     pub nrf_status: String,
+    pub nrf_endpoints: Vec<NrfEndpointStatus>,
+    pub database: DatabaseStatus,
     pub cache: CacheStats,
     pub load_balancer: LoadBalancerStats,
 }
@@ -61,3 +166,170 @@ pub struct StatusResponse {
 pub struct CacheStats {
     pub cached_nf_profiles: usize,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseStatus {
+    pub backend: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+async fn database_status(state: &AppState) -> DatabaseStatus {
+    let backend = state.state_store.backend().to_string();
+
+    if !state.state_store.is_shared() {
+        return DatabaseStatus {
+            backend,
+            status: "not_used".to_string(),
+            error: None,
+        };
+    }
+
+    let result = tokio::time::timeout(Duration::from_secs(1), state.state_store.ping())
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("ping timed out")));
+
+    match result {
+        Ok(()) => DatabaseStatus {
+            backend,
+            status: "connected".to_string(),
+            error: None,
+        },
+        Err(e) => DatabaseStatus {
+            backend,
+            status: "disconnected".to_string(),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn nrf_registration_check(state: &AppState) -> ReadinessCheck {
+    let Some(registration) = &state.nrf_registration else {
+        return ReadinessCheck::skipped("nrf_registration", "NRF not configured");
+    };
+
+    ReadinessCheck::from_result(
+        "nrf_registration",
+        if registration.is_registered() {
+            Ok(None)
+        } else {
+            Err("not registered with NRF".to_string())
+        },
+    )
+}
+
+fn nrf_heartbeat_check(state: &AppState) -> ReadinessCheck {
+    let Some(registration) = &state.nrf_registration else {
+        return ReadinessCheck::skipped("nrf_heartbeat", "NRF not configured");
+    };
+
+    let max_age = registration.heartbeat_interval() * state.readiness.max_missed_heartbeats.max(1);
+
+    let result = match registration.last_heartbeat() {
+        Some(last) if last.elapsed() <= max_age => {
+            Ok(Some(format!("last heartbeat {}s ago", last.elapsed().as_secs())))
+        }
+        Some(last) => Err(format!(
+            "last heartbeat {}s ago exceeds {}s",
+            last.elapsed().as_secs(),
+            max_age.as_secs()
+        )),
+        None => Err("no successful heartbeat yet".to_string()),
+    };
+
+    ReadinessCheck::from_result("nrf_heartbeat", result)
+}
+
+async fn database_check(state: &AppState) -> ReadinessCheck {
+    let database = database_status(state).await;
+
+    match database.status.as_str() {
+        "not_used" => ReadinessCheck::skipped("database", "no shared state store configured"),
+        "connected" => ReadinessCheck::from_result("database", Ok(Some(database.backend))),
+        _ => ReadinessCheck::from_result(
+            "database",
+            Err(database.error.unwrap_or_else(|| "disconnected".to_string())),
+        ),
+    }
+}
+
+fn tls_certificate_check(state: &AppState) -> ReadinessCheck {
+    let Some(cert_path) = &state.readiness.tls_cert_path else {
+        return ReadinessCheck::skipped("tls_certificate", "TLS not enabled");
+    };
+
+    ReadinessCheck::from_result("tls_certificate", certificate_validity(cert_path))
+}
+
+fn certificate_validity(cert_path: &str) -> Result<Option<String>, String> {
+    let pem = std::fs::read(cert_path).map_err(|e| format!("failed to read {}: {}", cert_path, e))?;
+
+    let der = rustls_pemfile::certs(&mut pem.as_slice())
+        .next()
+        .ok_or_else(|| format!("no certificate found in {}", cert_path))?
+        .map_err(|e| format!("failed to parse {}: {}", cert_path, e))?;
+
+    let (_, certificate) = x509_parser::parse_x509_certificate(&der)
+        .map_err(|e| format!("failed to parse {}: {}", cert_path, e))?;
+
+    let validity = certificate.validity();
+    if !validity.is_valid() {
+        return Err(format!(
+            "certificate not valid (valid from {} to {})",
+            validity.not_before, validity.not_after
+        ));
+    }
+
+    Ok(Some(format!("valid until {}", validity.not_after)))
+}
+
+async fn critical_nf_type_checks(state: &AppState) -> Vec<ReadinessCheck> {
+    let mut checks = Vec::with_capacity(state.readiness.critical_nf_types.len());
+
+    for nf_type in &state.readiness.critical_nf_types {
+        let mut known = registered_producers(state, nf_type);
+
+        if known == 0 {
+            if let Some(nrf_client) = &state.nrf_client {
+                let params = NfDiscoveryParams {
+                    target_nf_type: nf_type.clone(),
+                    requester_nf_type: Some("SCP".to_string()),
+                    service_names: None,
+                };
+
+                match tokio::time::timeout(Duration::from_secs(2), nrf_client.discover(&params)).await {
+                    Ok(Ok(instances)) => {
+                        for instance in instances {
+                            state.nf_profile_cache.insert(instance);
+                        }
+                        known = registered_producers(state, nf_type);
+                    }
+                    Ok(Err(e)) => tracing::debug!("Readiness discovery for {} failed: {}", nf_type, e),
+                    Err(_) => tracing::debug!("Readiness discovery for {} timed out", nf_type),
+                }
+            }
+        }
+
+        checks.push(ReadinessCheck::from_result(
+            &format!("producers_{}", nf_type.to_lowercase()),
+            if known > 0 {
+                Ok(Some(format!("{} known", known)))
+            } else {
+                Err(format!("no registered {} producer known", nf_type))
+            },
+        ));
+    }
+
+    checks
+}
+
+fn registered_producers(state: &AppState, nf_type: &str) -> usize {
+    state
+        .nf_profile_cache
+        .profiles()
+        .iter()
+        .filter(|profile| profile.nf_type.eq_ignore_ascii_case(nf_type))
+        .filter(|profile| profile.nf_status == "REGISTERED")
+        .count()
+}
@@ -0,0 +1,12 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+use crate::types::AppState;
+
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    state.retry_budget.export_metrics();
+    state.load_balancer.export_metrics();
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
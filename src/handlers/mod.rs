@@ -1,7 +1,7 @@
+pub mod admin;
+pub mod circuit_breaker;
 pub mod health;
+pub mod metrics;
 pub mod proxy;
+pub mod rate_limit;
 pub mod notification;
-
-pub use health::*;
-pub use proxy::*;
-pub use notification::*;
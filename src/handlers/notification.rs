@@ -1,14 +1,15 @@
 use axum::{
-    extract::State,
+    extract::{rejection::JsonRejection, State},
     http::StatusCode,
     Json,
 };
-use crate::types::{AppState, NrfNotification, NotificationEventType, CachedNfProfile};
+use crate::types::{AppError, AppState, NrfNotification, NotificationEventType};
 
 pub async fn handle_nrf_notification(
     State(state): State<AppState>,
-    Json(notification): Json<NrfNotification>,
-) -> Result<StatusCode, StatusCode> {
+    payload: Result<Json<NrfNotification>, JsonRejection>,
+) -> Result<StatusCode, AppError> {
+    let Json(notification) = payload?;
     tracing::info!("Received NRF notification: {:?}", notification.event);
 
     let nf_instance_id = extract_nf_instance_id(&notification.nf_instance_uri);
@@ -18,12 +19,7 @@ pub async fn handle_nrf_notification(
             if let Some(profile) = notification.nf_profile {
                 tracing::info!("Updating cache for NF instance: {}", nf_instance_id);
 
-                let cached_profile = CachedNfProfile {
-                    profile,
-                    cached_at: chrono::Utc::now(),
-                };
-
-                state.nf_profile_cache.insert(nf_instance_id.clone(), cached_profile);
+                state.nf_profile_cache.insert(profile);
             } else {
                 tracing::warn!("Received {} event without NF profile",
                     match notification.event {
@@ -43,12 +39,7 @@ pub async fn handle_nrf_notification(
                 tracing::info!("Updating NF status for instance: {} to {}",
                     nf_instance_id, profile.nf_status);
 
-                let cached_profile = CachedNfProfile {
-                    profile,
-                    cached_at: chrono::Utc::now(),
-                };
-
-                state.nf_profile_cache.insert(nf_instance_id.clone(), cached_profile);
+                state.nf_profile_cache.insert(profile);
             } else {
                 tracing::warn!("Received NfStatusChanged event without NF profile");
             }
@@ -61,7 +52,7 @@ pub async fn handle_nrf_notification(
 fn extract_nf_instance_id(nf_instance_uri: &str) -> String {
     nf_instance_uri
         .split('/')
-        .last()
+        .next_back()
         .unwrap_or(nf_instance_uri)
         .to_string()
 }
@@ -1,13 +1,70 @@
 use axum::{
     body::Body,
-    extract::{Request, State, ConnectInfo},
-    http::{HeaderMap, HeaderValue, Method, StatusCode, Uri},
+    extract::{State, ConnectInfo},
+    http::{header::{AUTHORIZATION, RETRY_AFTER, SERVER}, HeaderMap, HeaderValue, Method, StatusCode, Uri},
     response::{IntoResponse, Response},
 };
+use axum::body::Bytes;
 use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 use crate::clients::nrf::NfDiscoveryParams;
-use crate::types::{AppError, AppState};
-use crate::utils::retry_with_backoff;
+use crate::services::callback_router::CallbackTarget;
+use crate::services::scp_router::NextHop;
+use crate::services::session_store::Binding;
+use crate::types::{AppError, AppState, StatusAction};
+use crate::utils::{parse_retry_after, retry_with_backoff, Deadline, RetryDecision};
+use crate::utils::sbi_headers;
+
+struct ProxiedRequest<'a> {
+    method: Method,
+    path: &'a str,
+    query: Option<&'a str>,
+    headers: HeaderMap,
+    body: Bytes,
+    deadline: Deadline,
+}
+
+struct UpstreamResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+struct ForwardFailure {
+    error: AppError,
+    sent: bool,
+    status: Option<StatusCode>,
+    retry_after: Option<Duration>,
+    reselect: bool,
+    upstream: Option<UpstreamResponse>,
+}
+
+impl ForwardFailure {
+    fn not_sent(error: AppError) -> Self {
+        Self {
+            error,
+            sent: false,
+            status: None,
+            retry_after: None,
+            reselect: false,
+            upstream: None,
+        }
+    }
+
+    fn producer_signalled_safe(&self) -> bool {
+        self.status == Some(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    fn into_response(self) -> Result<Response, AppError> {
+        match self.upstream {
+            Some(upstream) => {
+                tracing::debug!("Returning last upstream response with status {}", upstream.status);
+                assemble_response(upstream.status, &upstream.headers, upstream.body)
+            }
+            None => Err(self.error),
+        }
+    }
+}
 
 pub async fn proxy_request(
     State(state): State<AppState>,
@@ -16,30 +73,151 @@ pub async fn proxy_request(
     uri: Uri,
     headers: HeaderMap,
     body: Body,
+) -> Response {
+    let Some(_in_flight) = state.lifecycle.admit() else {
+        return synthesized_error(&state, AppError::ServiceUnavailable("SCP is shutting down".to_string()));
+    };
+
+    let nf_type = extract_nf_type_from_path(uri.path());
+    let priority = sbi_headers::message_priority(&headers);
+    let _admission = match state.admission.try_admit(nf_type.as_deref(), priority) {
+        Ok(permit) => permit,
+        Err(detail) => {
+            let mut response = synthesized_error(&state, AppError::InsufficientResources(detail));
+            let retry_after = HeaderValue::from(state.admission.retry_after().as_secs());
+            response.headers_mut().insert(RETRY_AFTER, retry_after);
+            state.admission.annotate(response.headers_mut());
+            return response;
+        }
+    };
+
+    if state.rate_limiter.is_enabled() {
+        let consumer = state.rate_limiter.consumer_identity(&headers, addr);
+        let service_name = uri.path().split('/').nth(1).unwrap_or_default();
+        let target_nf_type = nf_type.as_deref().unwrap_or("UNKNOWN");
+
+        if let Err(exceeded) = state.rate_limiter.check(&consumer, target_nf_type, service_name).await {
+            tracing::warn!("Rate limiting {} {}: {}", method, uri.path(), exceeded.detail);
+            let mut response = synthesized_error(&state, AppError::TooManyRequests(exceeded.detail));
+            let retry_after = HeaderValue::from(exceeded.retry_after.as_secs());
+            response.headers_mut().insert(RETRY_AFTER, retry_after);
+            state.admission.annotate(response.headers_mut());
+            return response;
+        }
+    }
+
+    let mut response = match route_request(&state, addr, method, uri, headers, body).await {
+        Ok(response) => response,
+        Err(error) => synthesized_error(&state, error),
+    };
+    state.admission.annotate(response.headers_mut());
+    response
+}
+
+fn synthesized_error(state: &AppState, error: AppError) -> Response {
+    let mut response = error.into_response();
+    if let Ok(server) = HeaderValue::from_str(state.scp_router.local_identity()) {
+        response.headers_mut().insert(SERVER, server);
+    }
+    response
+}
+
+async fn route_request(
+    state: &AppState,
+    addr: SocketAddr,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Body,
 ) -> Result<Response, AppError> {
-    let path = uri.path();
-    let query = uri.query();
+    let deadline = Deadline::after(
+        sbi_headers::max_rsp_time_ms(&headers)
+            .map(Duration::from_millis)
+            .unwrap_or(state.default_max_rsp_time),
+    );
+
+    let body_bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to read request body: {}", e)))?;
+
+    let mut request = ProxiedRequest {
+        method,
+        path: uri.path(),
+        query: uri.query(),
+        headers,
+        body: body_bytes,
+        deadline,
+    };
 
-    tracing::debug!("Proxying {} request to {}", method, path);
+    let via = sbi_headers::via_received_by(&request.headers);
+
+    if let Some(reason) = state.scp_router.detect_loop(&via) {
+        tracing::warn!("Rejecting {} {}: {}", request.method, request.path, reason);
+        state
+            .metrics
+            .increment_counter("scp_loop_detected_total", &[("method", request.method.as_str())]);
+        return Err(AppError::LoopDetected(reason));
+    }
 
-    let target_nf_type = extract_nf_type_from_path(path).ok_or_else(|| {
-        AppError::BadRequest(format!("Unable to determine target NF type from path: {}", path))
+    if let Some(callback_type) = sbi_headers::callback_type(&request.headers) {
+        return forward_callback(state, &callback_type, &request).await;
+    }
+
+    tracing::debug!("Proxying {} request to {}", request.method, request.path);
+
+    if let Some(api_root) = sbi_headers::target_api_root(&request.headers) {
+        let next_hops = state.scp_router.next_hops_for_api_root(&api_root, &via);
+        if !next_hops.is_empty() {
+            return forward_to_next_hop(state, &request, &next_hops, &api_root).await;
+        }
+    }
+
+    let target_nf_type = extract_nf_type_from_path(request.path).ok_or_else(|| {
+        AppError::BadRequest(format!("Unable to determine target NF type from path: {}", request.path))
     })?;
 
     tracing::debug!("Extracted target NF type: {}", target_nf_type);
 
     let session_id = addr.ip().to_string();
 
-    let body_bytes = axum::body::to_bytes(body, usize::MAX)
-        .await
-        .map_err(|e| AppError::InternalError(format!("Failed to read request body: {}", e)))?;
+    let available_producers = tokio::time::timeout(
+        deadline.remaining(),
+        discover_producers(state, &target_nf_type),
+    )
+    .await
+    .map_err(|_| deadline_exceeded(&deadline))??;
+
+    state.retry_budget.record_request(&target_nf_type);
+
+    attach_access_token(state, &mut request, &target_nf_type, &available_producers).await;
+
+    let bound_instance = tokio::time::timeout(
+        deadline.remaining(),
+        restore_affinity(state, &request, &session_id, &target_nf_type),
+    )
+    .await
+    .unwrap_or_else(|_| {
+        tracing::warn!("Timed out restoring affinity for {} from shared store", request.path);
+        None
+    });
 
-    let available_producers = discover_producers(&state, &target_nf_type).await?;
     let mut excluded_instances = Vec::new();
+    let mut last_failure = None;
 
     for attempt in 0..available_producers.len() {
+        if deadline.is_expired() {
+            return Err(deadline_exceeded(&deadline));
+        }
+
         let (producer_uri, selected_instance_id, _connection_guard) =
-            match select_next_producer(&state, &target_nf_type, &session_id, &available_producers, &excluded_instances) {
+            match select_next_producer(
+                state,
+                &target_nf_type,
+                &session_id,
+                bound_instance.as_deref(),
+                &available_producers,
+                &excluded_instances,
+            ) {
                 Ok(producer) => producer,
                 Err(e) => {
                     tracing::error!("No more producers available to try: {}", e);
@@ -47,107 +225,713 @@ pub async fn proxy_request(
                 }
             };
 
+        let selected_profile = available_producers
+            .iter()
+            .find(|profile| profile.nf_instance_id == selected_instance_id);
+
+        let next_hops = selected_profile
+            .filter(|profile| state.scp_router.requires_next_hop(profile))
+            .map(|profile| state.scp_router.next_hops_for_producer(profile, &via));
+
+        let reselected = attempt > 0
+            || bound_instance
+                .as_deref()
+                .is_some_and(|bound| bound != selected_instance_id);
+        let (producer_id, producer_headers) =
+            producer_identity_headers(&request, &selected_instance_id, selected_profile, reselected);
+
         tracing::info!(
-            "Forwarding {} {} to producer at {} (attempt {}/{})",
-            method,
-            path,
+            "Forwarding {} {} to producer at {}{} (attempt {}/{})",
+            request.method,
+            request.path,
             producer_uri,
+            if next_hops.is_some() { " via next-hop SCP" } else { "" },
             attempt + 1,
             available_producers.len()
         );
 
-        let target_url = if let Some(q) = query {
-            format!("{}{}?{}", producer_uri, path, q)
-        } else {
-            format!("{}{}", producer_uri, path)
+        let retry_result = match &next_hops {
+            Some(next_hops) => {
+                send_via_next_hop(state, &request, next_hops, &producer_uri, &producer_headers, &target_nf_type).await
+            }
+            None => {
+                let target_url = build_target_url(&producer_uri, request.path, request.query);
+                send_with_retry(
+                    state,
+                    &request,
+                    &target_url,
+                    &selected_instance_id,
+                    &target_nf_type,
+                    &producer_headers,
+                )
+                .await
+            }
         };
 
-        let retry_result = retry_with_backoff(&state.retry_config, || {
-            let state = state.clone();
-            let target_url = target_url.clone();
-            let method = method.clone();
-            let headers = headers.clone();
-            let body_bytes = body_bytes.clone();
-            let selected_instance_id = selected_instance_id.clone();
-
-            async move {
-                let mut request_builder = state
-                    .http_client
-                    .request(method, &target_url);
-
-                for (key, value) in headers.iter() {
-                    if !is_hop_by_hop_header(key.as_str()) {
-                        request_builder = request_builder.header(key, value);
-                    }
+        match retry_result {
+            Ok(response) => {
+                if is_passed_through(&response) {
+                    tracing::debug!(
+                        "Passing through status {} from producer {}",
+                        response.status(),
+                        selected_instance_id
+                    );
+                    state
+                        .load_balancer
+                        .mark_passed_through(&selected_instance_id, &target_nf_type, response.status());
+                } else {
+                    state.load_balancer.mark_success(&selected_instance_id, &target_nf_type);
+                    persist_affinity(state, &request, &response, &session_id, &selected_instance_id, &target_nf_type);
+                }
+                let mut response = build_response(response).await?;
+                if !response.headers().contains_key(sbi_headers::SBI_PRODUCER_ID) {
+                    response.headers_mut().insert(sbi_headers::SBI_PRODUCER_ID, producer_id);
                 }
+                return Ok(response);
+            }
+            Err(failure) => {
+                state.load_balancer.mark_failure(&selected_instance_id, &target_nf_type);
 
-                if !body_bytes.is_empty() {
-                    request_builder = request_builder.body(body_bytes);
+                if let Some(retry_after) = failure.retry_after {
+                    state.load_balancer.mark_unavailable_for(&selected_instance_id, retry_after);
                 }
 
-                let response = request_builder.send().await.map_err(|e| {
-                    AppError::ServiceUnavailable(format!("Request failed: {}", e))
-                })?;
+                if deadline.is_expired() {
+                    tracing::warn!(
+                        "Producer {} failed and request budget is exhausted: {}",
+                        selected_instance_id,
+                        failure.error
+                    );
+                    if failure.upstream.is_some() {
+                        return failure.into_response();
+                    }
+                    return Err(deadline_exceeded(&deadline));
+                }
 
-                let status = response.status();
+                if failure.sent
+                    && !state.retry_config.allows_retry_after_send(
+                        &request.method,
+                        request.path,
+                        failure.producer_signalled_safe(),
+                    )
+                {
+                    tracing::warn!(
+                        "Producer {} failed after the {} request was sent: {}. Not reselecting a non-idempotent request",
+                        selected_instance_id,
+                        request.method,
+                        failure.error
+                    );
+                    return failure.into_response();
+                }
 
-                if status.is_server_error() || status == StatusCode::SERVICE_UNAVAILABLE {
+                if attempt + 1 < available_producers.len() && !state.retry_budget.try_acquire(&target_nf_type) {
                     tracing::warn!(
-                        "Producer {} returned error status {}, will retry",
+                        "Producer {} failed: {}. Retry budget for {} exhausted, not reselecting",
                         selected_instance_id,
-                        status
+                        failure.error,
+                        target_nf_type
                     );
-                    return Err(AppError::ServiceUnavailable(format!(
-                        "Producer returned error status: {}",
-                        status
-                    )));
+                    return failure.into_response();
                 }
 
-                Ok(response)
+                tracing::warn!(
+                    "Producer {} failed after retries: {}. Trying next producer...",
+                    selected_instance_id,
+                    failure.error
+                );
+                excluded_instances.push(selected_instance_id);
+                last_failure = Some(failure);
             }
-        })
-        .await;
+        }
+    }
 
-        match retry_result {
-            Ok(response) => {
-                let status = response.status();
-                state.load_balancer.mark_success(&selected_instance_id);
+    match last_failure {
+        Some(failure) if failure.upstream.is_some() => failure.into_response(),
+        _ => Err(AppError::NfFailover(
+            "All available producers failed to handle the request".to_string(),
+        )),
+    }
+}
+
+async fn forward_callback(
+    state: &AppState,
+    callback_type: &str,
+    request: &ProxiedRequest<'_>,
+) -> Result<Response, AppError> {
+    let api_root = sbi_headers::target_api_root(&request.headers).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Callback {} received without {} header",
+            callback_type,
+            sbi_headers::SBI_TARGET_API_ROOT
+        ))
+    })?;
 
-                let response_headers = response.headers().clone();
-                let response_body = response
-                    .bytes()
-                    .await
-                    .map_err(|e| AppError::InternalError(format!("Failed to read response body: {}", e)))?;
+    let api_root_url = url::Url::parse(&api_root).map_err(|e| {
+        AppError::BadRequest(format!("Invalid {} header {}: {}", sbi_headers::SBI_TARGET_API_ROOT, api_root, e))
+    })?;
 
-                let mut builder = Response::builder().status(status);
+    let target_nf_type = state.callback_router.resolve_nf_type(callback_type).ok_or_else(|| {
+        AppError::BadRequest(format!("Callback type {} does not map to a target NF type", callback_type))
+    })?;
 
-                for (key, value) in response_headers.iter() {
-                    if !is_hop_by_hop_header(key.as_str()) {
-                        builder = builder.header(key, value);
-                    }
+    let target = resolve_callback_target(state, &api_root_url, &target_nf_type, &request.deadline)
+        .await
+        .ok_or_else(|| {
+            tracing::warn!(
+                "Rejecting {} callback to {}: not a known {} instance or allowed apiRoot",
+                callback_type,
+                api_root,
+                target_nf_type
+            );
+            AppError::Forbidden(format!(
+                "Callback target {} is not a known {} instance",
+                api_root, target_nf_type
+            ))
+        })?;
+
+    state.retry_budget.record_request(&target_nf_type);
+
+    tracing::info!(
+        "Forwarding {} callback {} {} to {} (target NF type: {}, target: {:?})",
+        callback_type,
+        request.method,
+        request.path,
+        api_root,
+        target_nf_type,
+        target
+    );
+
+    let target_url = build_target_url(&api_root, request.path, request.query);
+    let (target_id, tracked_instance) = match &target {
+        CallbackTarget::Instance(nf_instance_id) => (nf_instance_id.as_str(), Some(nf_instance_id.as_str())),
+        CallbackTarget::Allowed(allowed_api_root) => (allowed_api_root.as_str(), None),
+    };
+    let _connection_guard =
+        tracked_instance.map(|nf_instance_id| state.load_balancer.acquire_connection(nf_instance_id.to_string()));
+
+    match send_with_retry(state, request, &target_url, target_id, &target_nf_type, &HeaderMap::new()).await {
+        Ok(response) => {
+            if let Some(nf_instance_id) = tracked_instance {
+                if is_passed_through(&response) {
+                    state.load_balancer.mark_passed_through(nf_instance_id, &target_nf_type, response.status());
+                } else {
+                    state.load_balancer.mark_success(nf_instance_id, &target_nf_type);
                 }
+            }
+            build_response(response).await
+        }
+        Err(failure) => {
+            if let Some(nf_instance_id) = tracked_instance {
+                state.load_balancer.mark_failure(nf_instance_id, &target_nf_type);
+            }
+            tracing::warn!("Callback {} to {} failed after retries: {}", callback_type, api_root, failure.error);
+            if request.deadline.is_expired() && failure.upstream.is_none() {
+                return Err(deadline_exceeded(&request.deadline));
+            }
+            failure.into_response()
+        }
+    }
+}
+
+async fn resolve_callback_target(
+    state: &AppState,
+    api_root: &url::Url,
+    target_nf_type: &str,
+    deadline: &Deadline,
+) -> Option<CallbackTarget> {
+    let cached = state.nf_profile_cache.profiles();
+    if let Some(target) = state.callback_router.match_target(api_root, target_nf_type, &cached) {
+        return Some(target);
+    }
+
+    match tokio::time::timeout(deadline.remaining(), discover_producers(state, target_nf_type)).await {
+        Ok(Ok(discovered)) => state.callback_router.match_target(api_root, target_nf_type, &discovered),
+        Ok(Err(e)) => {
+            tracing::debug!("Discovery of {} for callback target {} failed: {}", target_nf_type, api_root, e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+async fn forward_to_next_hop(
+    state: &AppState,
+    request: &ProxiedRequest<'_>,
+    next_hops: &[NextHop],
+    target_api_root: &str,
+) -> Result<Response, AppError> {
+    let target_nf_type = extract_nf_type_from_path(request.path).unwrap_or_else(|| "UNKNOWN".to_string());
+
+    state.retry_budget.record_request(&target_nf_type);
+
+    match send_via_next_hop(state, request, next_hops, target_api_root, &HeaderMap::new(), &target_nf_type).await {
+        Ok(response) => build_response(response).await,
+        Err(failure) => {
+            if request.deadline.is_expired() && failure.upstream.is_none() {
+                return Err(deadline_exceeded(&request.deadline));
+            }
+            failure.into_response()
+        }
+    }
+}
+
+async fn send_via_next_hop(
+    state: &AppState,
+    request: &ProxiedRequest<'_>,
+    next_hops: &[NextHop],
+    target_api_root: &str,
+    extra_headers: &HeaderMap,
+    target_nf_type: &str,
+) -> Result<reqwest::Response, ForwardFailure> {
+    let mut next_hop_headers = extra_headers.clone();
+    match HeaderValue::from_str(target_api_root) {
+        Ok(value) => {
+            next_hop_headers.insert(sbi_headers::SBI_TARGET_API_ROOT, value);
+        }
+        Err(e) => {
+            return Err(ForwardFailure::not_sent(AppError::BadRequest(format!(
+                "Invalid target apiRoot {}: {}",
+                target_api_root, e
+            ))))
+        }
+    }
+
+    let mut last_failure = None;
 
-                let response = builder
-                    .body(Body::from(response_body))
-                    .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+    for next_hop in next_hops {
+        if request.deadline.is_expired() {
+            return Err(ForwardFailure::not_sent(deadline_exceeded(&request.deadline)));
+        }
+
+        tracing::info!(
+            "Forwarding {} {} for {} to next-hop SCP {}",
+            request.method,
+            request.path,
+            target_api_root,
+            next_hop.uri
+        );
 
+        let target_url = build_target_url(&next_hop.uri, request.path, request.query);
+
+        match send_with_retry(state, request, &target_url, &next_hop.id, target_nf_type, &next_hop_headers).await {
+            Ok(response) => {
+                if is_passed_through(&response) {
+                    if response.status().is_server_error() {
+                        state.scp_router.record_failure(next_hop);
+                    }
+                } else {
+                    state.scp_router.record_success(next_hop);
+                }
                 return Ok(response);
             }
-            Err(e) => {
-                state.load_balancer.mark_failure(&selected_instance_id);
+            Err(failure) => {
+                state.scp_router.record_failure(next_hop);
+
+                if failure.sent
+                    && !state.retry_config.allows_retry_after_send(
+                        &request.method,
+                        request.path,
+                        failure.producer_signalled_safe(),
+                    )
+                {
+                    return Err(failure);
+                }
+
+                tracing::warn!("Next-hop SCP {} failed: {}", next_hop.uri, failure.error);
+                last_failure = Some(failure);
+            }
+        }
+    }
+
+    Err(last_failure.unwrap_or_else(|| {
+        ForwardFailure::not_sent(AppError::TargetNfNotReachable(format!(
+            "No next-hop SCP available for {}",
+            target_api_root
+        )))
+    }))
+}
+
+async fn attach_access_token(
+    state: &AppState,
+    request: &mut ProxiedRequest<'_>,
+    target_nf_type: &str,
+    producers: &[crate::types::NfProfile],
+) {
+    let Some(nrf_client) = state.nrf_client.as_ref().filter(|nrf_client| nrf_client.issues_access_tokens()) else {
+        return;
+    };
+
+    if request.headers.contains_key(AUTHORIZATION) {
+        return;
+    }
+
+    let Some(scope) = request.path.split('/').find(|segment| !segment.is_empty()) else {
+        return;
+    };
+
+    if !producers.iter().any(|producer| producer.service_instance_id(scope).is_some()) {
+        tracing::debug!(
+            "No discovered {} producer offers service {}, forwarding without an access token",
+            target_nf_type,
+            scope
+        );
+        return;
+    }
+
+    let token = tokio::time::timeout(
+        request.deadline.remaining(),
+        nrf_client.access_token(target_nf_type, scope),
+    )
+    .await;
+
+    match token {
+        Ok(Ok(token)) => match HeaderValue::from_str(&format!("Bearer {}", token)) {
+            Ok(value) => {
+                request.headers.insert(AUTHORIZATION, value);
+            }
+            Err(e) => tracing::warn!("Access token for {} is not a valid header value: {}", scope, e),
+        },
+        Ok(Err(e)) => tracing::warn!(
+            "Failed to obtain access token for {} ({}), forwarding without one: {}",
+            target_nf_type,
+            scope,
+            e
+        ),
+        Err(_) => tracing::warn!(
+            "Timed out obtaining access token for {} ({}), forwarding without one",
+            target_nf_type,
+            scope
+        ),
+    }
+}
+
+async fn restore_affinity(
+    state: &AppState,
+    request: &ProxiedRequest<'_>,
+    session_id: &str,
+    target_nf_type: &str,
+) -> Option<String> {
+    if !state.load_balancer.has_sticky_session(session_id) {
+        if let Some(nf_instance_id) = state.session_store.load_sticky_session(session_id, target_nf_type).await {
+            state
+                .load_balancer
+                .restore_sticky_session(session_id, &nf_instance_id, target_nf_type);
+        }
+    }
+
+    if let Some(nf_instance_id) = sbi_headers::routing_binding_nf_instance(&request.headers) {
+        tracing::debug!("Routing binding header targets NF instance {}", nf_instance_id);
+        return Some(nf_instance_id);
+    }
+
+    state
+        .session_store
+        .resolve_binding(request.path)
+        .await
+        .filter(|binding| binding.nf_type == target_nf_type)
+        .map(|binding| binding.nf_instance_id)
+}
+
+fn persist_affinity(
+    state: &AppState,
+    request: &ProxiedRequest<'_>,
+    response: &reqwest::Response,
+    session_id: &str,
+    nf_instance_id: &str,
+    target_nf_type: &str,
+) {
+    if state.session_store.is_shared() {
+        if let Some((session_instance_id, session_nf_type)) = state
+            .load_balancer
+            .take_session_for_persist(session_id, state.session_store.write_interval())
+        {
+            state
+                .session_store
+                .persist_sticky_session(session_id, &session_instance_id, &session_nf_type);
+        }
+    }
+
+    let Some(binding_header) = response
+        .headers()
+        .get(sbi_headers::SBI_BINDING)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return;
+    };
+
+    let resource_path = match response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(location) => url::Url::parse(location)
+            .map(|url| url.path().to_string())
+            .unwrap_or_else(|_| location.to_string()),
+        None if request.method != Method::POST => request.path.to_string(),
+        None => return,
+    };
+
+    let mut parameters = sbi_headers::binding_parameters(binding_header);
+
+    state.session_store.record_binding(
+        &resource_path,
+        Binding {
+            nf_instance_id: parameters
+                .remove("nfinst")
+                .unwrap_or_else(|| nf_instance_id.to_string()),
+            nf_type: target_nf_type.to_string(),
+            nf_set_id: parameters.remove("nfset"),
+            binding_level: parameters.remove("bl"),
+        },
+    );
+}
+
+fn producer_identity_headers(
+    request: &ProxiedRequest<'_>,
+    nf_instance_id: &str,
+    profile: Option<&crate::types::NfProfile>,
+    reselected: bool,
+) -> (HeaderValue, HeaderMap) {
+    let service_name = request
+        .path
+        .split('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or_default();
+    let nf_service_instance_id = profile.and_then(|profile| profile.service_instance_id(service_name));
+    let nf_set_id = profile.and_then(|profile| profile.nf_set_id_list.first().map(String::as_str));
+
+    let header_value = |value: String| {
+        HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("nfinst=unknown"))
+    };
+
+    let producer_id = header_value(sbi_headers::nf_identity(nf_instance_id, nf_service_instance_id, nf_set_id));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        sbi_headers::SBI_TARGET_NF_ID,
+        header_value(sbi_headers::nf_identity(nf_instance_id, nf_service_instance_id, None)),
+    );
+    if reselected {
+        headers.insert(sbi_headers::SBI_SELECTION_INFO, HeaderValue::from_static("reselection=true"));
+    }
+
+    (producer_id, headers)
+}
+
+fn deadline_exceeded(deadline: &Deadline) -> AppError {
+    AppError::GatewayTimeout(format!(
+        "Request could not be completed within {} ms",
+        deadline.budget().as_millis()
+    ))
+}
+
+fn build_target_url(api_root: &str, path: &str, query: Option<&str>) -> String {
+    if let Some(q) = query {
+        format!("{}{}?{}", api_root, path, q)
+    } else {
+        format!("{}{}", api_root, path)
+    }
+}
+
+async fn send_with_retry(
+    state: &AppState,
+    request: &ProxiedRequest<'_>,
+    target_url: &str,
+    target_id: &str,
+    target_nf_type: &str,
+    extra_headers: &HeaderMap,
+) -> Result<reqwest::Response, ForwardFailure> {
+    let classify = |failure: &ForwardFailure| {
+        if failure.reselect {
+            RetryDecision::Abort
+        } else if !failure.sent {
+            RetryDecision::Retry
+        } else if !state.retry_config.allows_retry_after_send(
+            &request.method,
+            request.path,
+            failure.producer_signalled_safe(),
+        ) {
+            RetryDecision::Abort
+        } else {
+            match failure.retry_after {
+                Some(retry_after) if retry_after > state.retry_config.max_backoff() => RetryDecision::Abort,
+                Some(retry_after) => RetryDecision::RetryAfter(retry_after),
+                None => RetryDecision::Retry,
+            }
+        }
+    };
+    let admit_retry = || state.retry_budget.try_acquire(target_nf_type);
+
+    retry_with_backoff(&state.retry_config, Some(&request.deadline), classify, admit_retry, || {
+        let state = state.clone();
+        let target_url = target_url.to_string();
+        let method = request.method.clone();
+        let headers = request.headers.clone();
+        let body_bytes = request.body.clone();
+        let target_id = target_id.to_string();
+        let target_nf_type = target_nf_type.to_string();
+        let extra_headers = extra_headers.clone();
+        let deadline = request.deadline;
+
+        async move {
+            if deadline.is_expired() {
+                return Err(ForwardFailure::not_sent(deadline_exceeded(&deadline)));
+            }
+
+            let mut request_builder = state
+                .http_client
+                .request(method, &target_url)
+                .timeout(deadline.remaining());
+
+            for (key, value) in headers.iter() {
+                if !is_hop_by_hop_header(key.as_str())
+                    && key.as_str() != sbi_headers::SBI_TARGET_API_ROOT
+                    && key.as_str() != sbi_headers::SBI_MAX_RSP_TIME
+                    && !extra_headers.contains_key(key)
+                {
+                    request_builder = request_builder.header(key, value);
+                }
+            }
+
+            request_builder = request_builder
+                .header(sbi_headers::SBI_MAX_RSP_TIME, deadline.remaining_ms().to_string())
+                .header(sbi_headers::VIA, format!("2.0 {}", state.scp_router.local_identity()));
+
+            for (key, value) in extra_headers.iter() {
+                request_builder = request_builder.header(key, value);
+            }
+
+            if !body_bytes.is_empty() {
+                request_builder = request_builder.body(body_bytes);
+            }
+
+            let started_at = Instant::now();
+            let send_result = request_builder.send().await;
+
+            let succeeded = matches!(&send_result, Ok(response) if !response.status().is_server_error());
+            state.outlier_detector.record(&target_id, &target_nf_type, succeeded, started_at.elapsed());
+
+            let response = send_result.map_err(|e| {
+                if e.is_connect() {
+                    ForwardFailure::not_sent(AppError::TargetNfNotReachable(format!("Request failed: {}", e)))
+                } else if e.is_timeout() {
+                    ForwardFailure {
+                        error: AppError::GatewayTimeout(format!("Request timed out: {}", e)),
+                        sent: true,
+                        status: None,
+                        retry_after: None,
+                        reselect: false,
+                        upstream: None,
+                    }
+                } else {
+                    ForwardFailure {
+                        error: AppError::TargetNfNotReachable(format!("Request failed: {}", e)),
+                        sent: true,
+                        status: None,
+                        retry_after: None,
+                        reselect: false,
+                        upstream: None,
+                    }
+                }
+            })?;
+
+            let status = response.status();
+            let action = state.retry_config.status_action(status);
+
+            if (status.is_client_error() || status.is_server_error()) && action != StatusAction::PassThrough {
                 tracing::warn!(
-                    "Producer {} failed after retries: {}. Trying next producer...",
-                    selected_instance_id,
-                    e
+                    "Producer {} returned error status {} ({:?})",
+                    target_id,
+                    status,
+                    action
                 );
-                excluded_instances.push(selected_instance_id);
+
+                let retry_after = if status == StatusCode::SERVICE_UNAVAILABLE || status == StatusCode::TOO_MANY_REQUESTS {
+                    response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(parse_retry_after)
+                } else {
+                    None
+                };
+
+                let headers = response.headers().clone();
+                let upstream = response.bytes().await.ok().map(|body| UpstreamResponse {
+                    status,
+                    headers,
+                    body,
+                });
+
+                return Err(ForwardFailure {
+                    error: AppError::ServiceUnavailable(format!(
+                        "Producer returned error status: {}",
+                        status
+                    )),
+                    sent: true,
+                    status: Some(status),
+                    retry_after,
+                    reselect: action == StatusAction::Reselect,
+                    upstream,
+                });
             }
+
+            Ok(response)
         }
+    })
+    .await
+}
+
+fn is_passed_through(response: &reqwest::Response) -> bool {
+    response.status().is_client_error() || response.status().is_server_error()
+}
+
+fn producer_redirect(response: &reqwest::Response) -> Option<AppError> {
+    let status = response.status();
+    if !matches!(status, StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT) {
+        return None;
     }
 
-    Err(AppError::ServiceUnavailable(
-        "All available producers failed to handle the request".to_string(),
-    ))
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|value| value.to_str().ok())?;
+    let location = response.url().join(location).ok()?;
+
+    Some(AppError::ScpRedirection {
+        status,
+        location: location.to_string(),
+    })
+}
+
+async fn build_response(response: reqwest::Response) -> Result<Response, AppError> {
+    if let Some(redirect) = producer_redirect(&response) {
+        tracing::info!("Producer {} answered {}, relaying the redirect to the consumer", response.url(), response.status());
+        return Err(redirect);
+    }
+
+    let status = response.status();
+    let response_headers = response.headers().clone();
+    let response_body = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to read response body: {}", e)))?;
+
+    assemble_response(status, &response_headers, response_body)
+}
+
+fn assemble_response(status: StatusCode, headers: &HeaderMap, body: Bytes) -> Result<Response, AppError> {
+    let mut builder = Response::builder().status(status);
+
+    for (key, value) in headers.iter() {
+        if !is_hop_by_hop_header(key.as_str()) {
+            builder = builder.header(key, value);
+        }
+    }
+
+    builder
+        .body(Body::from(body))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))
 }
 
 fn extract_nf_type_from_path(path: &str) -> Option<String> {
@@ -181,7 +965,7 @@ async fn discover_producers(
     let nrf_client = state
         .nrf_client
         .as_ref()
-        .ok_or_else(|| AppError::InternalError("NRF client not configured".to_string()))?;
+        .ok_or_else(|| AppError::NfDiscoveryFailure("NRF client not configured".to_string()))?;
 
     let params = NfDiscoveryParams {
         target_nf_type: target_nf_type.to_string(),
@@ -192,15 +976,19 @@ async fn discover_producers(
     let instances: Vec<crate::types::NfProfile> = nrf_client
         .discover(&params)
         .await
-        .map_err(|e| AppError::ServiceUnavailable(format!("NRF discovery failed: {}", e)))?;
+        .map_err(|e| AppError::NfDiscoveryFailure(format!("NRF discovery failed: {}", e)))?;
 
     if instances.is_empty() {
-        return Err(AppError::ServiceUnavailable(format!(
+        return Err(AppError::NfDiscoveryFailure(format!(
             "No available instances found for NF type: {}",
             target_nf_type
         )));
     }
 
+    for instance in &instances {
+        state.nf_profile_cache.insert(instance.clone());
+    }
+
     Ok(instances)
 }
 
@@ -208,25 +996,45 @@ fn select_next_producer(
     state: &AppState,
     target_nf_type: &str,
     session_id: &str,
+    bound_instance: Option<&str>,
     instances: &[crate::types::NfProfile],
     excluded_instances: &[String],
 ) -> Result<(String, String, crate::services::load_balancer::ConnectionGuard), AppError> {
     let available_instances: Vec<_> = instances
         .iter()
         .filter(|i| !excluded_instances.contains(&i.nf_instance_id))
+        .filter(|i| !state.load_balancer.is_disabled(&i.nf_instance_id))
         .cloned()
         .collect();
 
     if available_instances.is_empty() {
-        return Err(AppError::ServiceUnavailable(
+        return Err(AppError::NfFailover(
             "No more available producer instances to try".to_string(),
         ));
     }
 
-    let selected = state
-        .load_balancer
-        .select_with_sticky_session(session_id, target_nf_type, &available_instances)
-        .clone();
+    let bound = bound_instance.and_then(|nf_instance_id| {
+        available_instances
+            .iter()
+            .find(|i| i.nf_instance_id == nf_instance_id && state.load_balancer.get_health_status(nf_instance_id))
+    });
+
+    let selected = match bound {
+        Some(instance) => {
+            tracing::debug!("Using bound NF instance {}", instance.nf_instance_id);
+            instance.clone()
+        }
+        None => state
+            .load_balancer
+            .select_with_sticky_session(session_id, target_nf_type, &available_instances)
+            .ok_or_else(|| {
+                AppError::NfFailover(format!(
+                    "No {} producer is accepting requests: circuits are open or ejected",
+                    target_nf_type
+                ))
+            })?
+            .clone(),
+    };
 
     let uri = build_producer_uri(&selected)?;
     let instance_id = selected.nf_instance_id.clone();
@@ -238,16 +1046,12 @@ fn select_next_producer(
 }
 
 fn build_producer_uri(profile: &crate::types::NfProfile) -> Result<String, AppError> {
-    if let Some(fqdn) = &profile.fqdn {
-        Ok(format!("http://{}", fqdn))
-    } else if let Some(ip) = profile.ipv4_addresses.first() {
-        Ok(format!("http://{}", ip))
-    } else {
-        Err(AppError::InternalError(format!(
+    profile.api_root().ok_or_else(|| {
+        AppError::InternalError(format!(
             "No valid address found for NF instance {}",
             profile.nf_instance_id
-        )))
-    }
+        ))
+    })
 }
 
 fn is_hop_by_hop_header(name: &str) -> bool {
@@ -263,3 +1067,55 @@ fn is_hop_by_hop_header(name: &str) -> bool {
             | "upgrade"
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::{CONNECTION, CONTENT_TYPE, LOCATION};
+    use crate::types::PROBLEM_JSON;
+
+    #[tokio::test]
+    async fn producer_problem_details_pass_through_unchanged() {
+        let body = Bytes::from_static(br#"{"status":404,"cause":"USER_NOT_FOUND","detail":"imsi-1"}"#);
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        headers.insert(CONNECTION, HeaderValue::from_static("keep-alive"));
+
+        let failure = ForwardFailure {
+            upstream: Some(UpstreamResponse {
+                status: StatusCode::NOT_FOUND,
+                headers,
+                body: body.clone(),
+            }),
+            ..ForwardFailure::not_sent(AppError::ServiceUnavailable("Producer returned error status: 404".to_string()))
+        };
+
+        let response = failure.into_response().unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), PROBLEM_JSON);
+        assert!(response.headers().get(CONNECTION).is_none());
+        assert_eq!(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap(), body);
+    }
+
+    #[test]
+    fn producer_redirects_are_answered_as_scp_redirection() {
+        let redirect = |status: StatusCode| {
+            reqwest::Response::from(
+                axum::http::Response::builder()
+                    .status(status)
+                    .header(LOCATION, "http://udm-2.example/nudm-sdm/v2/imsi-1")
+                    .body(Vec::new())
+                    .unwrap(),
+            )
+        };
+
+        match producer_redirect(&redirect(StatusCode::TEMPORARY_REDIRECT)) {
+            Some(AppError::ScpRedirection { status, location }) => {
+                assert_eq!(status, StatusCode::TEMPORARY_REDIRECT);
+                assert_eq!(location, "http://udm-2.example/nudm-sdm/v2/imsi-1");
+            }
+            other => panic!("expected an SCP redirection, got {:?}", other),
+        }
+        assert!(producer_redirect(&redirect(StatusCode::FOUND)).is_none());
+    }
+}
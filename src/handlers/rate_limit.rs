@@ -0,0 +1,44 @@
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    Json,
+};
+use serde::Serialize;
+use crate::types::{AppError, AppState, RateLimitRule};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitOverview {
+    pub enabled: bool,
+    pub shared: bool,
+    pub rules: Vec<RateLimitRule>,
+}
+
+pub async fn list_rate_limits(State(state): State<AppState>) -> Json<RateLimitOverview> {
+    Json(overview(&state))
+}
+
+pub async fn update_rate_limits(
+    State(state): State<AppState>,
+    payload: Result<Json<Vec<RateLimitRule>>, JsonRejection>,
+) -> Result<Json<RateLimitOverview>, AppError> {
+    let Json(rules) = payload?;
+
+    if let Some(invalid) = rules.iter().find(|rule| !rule.is_valid()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid rate limit rule for consumer {} and target {}: ratePerSecond must be positive and burst at least 1",
+            invalid.consumer, invalid.target
+        )));
+    }
+
+    state.rate_limiter.set_rules(rules);
+
+    Ok(Json(overview(&state)))
+}
+
+fn overview(state: &AppState) -> RateLimitOverview {
+    RateLimitOverview {
+        enabled: state.rate_limiter.is_enabled(),
+        shared: state.rate_limiter.is_shared(),
+        rules: state.rate_limiter.rules(),
+    }
+}
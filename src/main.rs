@@ -3,35 +3,16 @@ mod db;
 mod handlers;
 mod clients;
 mod services;
+mod store;
 mod types;
 mod utils;
 mod middleware;
 mod routes;
 
 use std::net::SocketAddr;
-use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use tokio::signal;
-use std::time::Duration;
-
-async fn heartbeat_task(
-    nrf_client: Arc<clients::nrf::NrfClient>,
-    profile: types::NfProfile,
-    interval_seconds: u64,
-) {
-    let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
-    interval.tick().await;
-
-    loop {
-        interval.tick().await;
-
-        match nrf_client.heartbeat(&profile).await {
-            Ok(_) => tracing::debug!("Heartbeat sent to NRF"),
-            Err(e) => tracing::warn!("Failed to send heartbeat to NRF: {}", e),
-        }
-    }
-}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -49,30 +30,70 @@ async fn main() -> anyhow::Result<()> {
 
     let state = db::init(&config).await?;
 
-    if let Some(ref nrf_client) = state.nrf_client {
-        let profile = types::NfProfile {
-            nf_instance_id: state.nf_instance_id.to_string(),
-            nf_type: "SCP".to_string(),
-            nf_status: "REGISTERED".to_string(),
-            ipv4_addresses: vec![config.scp_host.clone()],
-            fqdn: None,
-            capacity: Some(100),
-            priority: Some(1),
-        };
-
-        match nrf_client.register(&profile).await {
-            Ok(_) => tracing::info!("Successfully registered with NRF"),
-            Err(e) => tracing::error!("Failed to register with NRF: {}", e),
+    if let Some(registration) = state.nrf_registration.clone() {
+        registration.register().await;
+        tokio::spawn(registration.run_heartbeats());
+    }
+
+    if config.scp_routing.nrf_discovery {
+        match state.nrf_client.clone() {
+            Some(nrf_client) => {
+                tokio::spawn(state.scp_router.clone().run_discovery(nrf_client));
+            }
+            None => tracing::warn!("SCP_NEXT_HOP_DISCOVERY is set but no NRF is configured"),
         }
+    }
 
-        let heartbeat_client = nrf_client.clone();
-        let heartbeat_profile = profile.clone();
-        let heartbeat_interval = config.heartbeat_interval_seconds;
+    if config.health_check.enabled {
+        let health_check_state = state.clone();
+        let health_check_settings = config.health_check.clone();
         tokio::spawn(async move {
-            heartbeat_task(heartbeat_client, heartbeat_profile, heartbeat_interval).await;
+            services::health_checker::run(health_check_state, health_check_settings).await;
         });
     }
 
+    tokio::spawn(state.load_balancer.clone().run_session_sweeper());
+    tokio::spawn(
+        state
+            .session_store
+            .clone()
+            .run_sweeper(config.sticky_session.sweep_interval()),
+    );
+
+    if state.outlier_detector.is_enabled() {
+        tokio::spawn(state.outlier_detector.clone().run());
+    }
+
+    if state.rate_limiter.is_enabled() {
+        tokio::spawn(state.rate_limiter.clone().run_maintenance());
+    }
+
+    if config.admin.enabled {
+        match config.admin.api_token.clone() {
+            Some(api_token) => {
+                let admin_app = routes::create_admin_routes(state.clone(), api_token)
+                    .layer(TraceLayer::new_for_http());
+                let admin_addr: SocketAddr = format!("{}:{}", config.admin.host, config.admin.port).parse()?;
+                let admin_listener = tokio::net::TcpListener::bind(admin_addr).await?;
+
+                tracing::info!("SCP admin API listening on {}", admin_addr);
+
+                tokio::spawn(async move {
+                    if let Err(e) = axum::serve(admin_listener, admin_app)
+                        .with_graceful_shutdown(shutdown_signal())
+                        .await
+                    {
+                        tracing::error!("Admin API server failed: {}", e);
+                    }
+                });
+            }
+            None => tracing::error!("ADMIN_ENABLED is set but ADMIN_API_TOKEN is empty, admin API not started"),
+        }
+    }
+
+    let shutdown_state = state.clone();
+    let shutdown_config = config.shutdown.clone();
+
     let app = routes::create_routes(state)
         .layer(TraceLayer::new_for_http())
         .layer(tower_http::cors::CorsLayer::permissive())
@@ -85,7 +106,10 @@ async fn main() -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            services::lifecycle::shutdown(shutdown_state, shutdown_config).await;
+        })
         .await?;
 
     Ok(())
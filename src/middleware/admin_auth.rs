@@ -0,0 +1,39 @@
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use crate::types::AppError;
+
+#[derive(Clone)]
+pub struct AdminToken(pub Arc<String>);
+
+pub async fn require_admin_token(
+    State(token): State<AdminToken>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Unauthorized("Missing admin bearer token".to_string()))?;
+
+    if !constant_time_eq(provided.as_bytes(), token.0.as_bytes()) {
+        tracing::warn!("Rejected admin request to {} with invalid token", request.uri().path());
+        return Err(AppError::Unauthorized("Invalid admin bearer token".to_string()));
+    }
+
+    Ok(next.run(request).await)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,11 +1,40 @@
-use axum::{routing::{any, get, post}, Router};
-use crate::{handlers, types::AppState};
+use axum::{routing::{delete, get, post, put}, Router};
+use std::sync::Arc;
+use crate::{handlers, middleware::admin_auth::{self, AdminToken}, types::AppState};
 
 pub fn create_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/health", get(handlers::health::health_check))
+        .route("/health/live", get(handlers::health::liveness))
+        .route("/health/ready", get(handlers::health::readiness))
         .route("/status", get(handlers::health::status))
+        .route("/metrics", get(handlers::metrics::metrics))
         .route("/nrf-notify", post(handlers::notification::handle_nrf_notification))
         .fallback(handlers::proxy::proxy_request)
         .with_state(app_state)
 }
+
+pub fn create_admin_routes(app_state: AppState, api_token: String) -> Router {
+    Router::new()
+        .route("/admin/instances", get(handlers::admin::list_instances))
+        .route("/admin/instances/:nf_instance_id/drain", post(handlers::admin::drain_instance))
+        .route("/admin/instances/:nf_instance_id/disable", post(handlers::admin::disable_instance))
+        .route("/admin/instances/:nf_instance_id/enable", post(handlers::admin::enable_instance))
+        .route("/admin/instances/:nf_instance_id/circuit/reset", post(handlers::admin::reset_circuit))
+        .route("/admin/sticky-sessions", get(handlers::admin::list_sticky_sessions))
+        .route("/admin/sticky-sessions/:session_id", delete(handlers::admin::delete_sticky_session))
+        .route("/admin/circuit-breakers", get(handlers::circuit_breaker::list_circuit_breakers))
+        .route(
+            "/admin/circuit-breakers/config/:nf_type",
+            put(handlers::circuit_breaker::update_circuit_breaker_config),
+        )
+        .route(
+            "/admin/rate-limits",
+            get(handlers::rate_limit::list_rate_limits).put(handlers::rate_limit::update_rate_limits),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            AdminToken(Arc::new(api_token)),
+            admin_auth::require_admin_token,
+        ))
+        .with_state(app_state)
+}
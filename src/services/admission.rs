@@ -0,0 +1,239 @@
+use axum::http::{HeaderMap, HeaderValue};
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::types::{AdmissionConfig, DEFAULT_MESSAGE_PRIORITY, LOWEST_MESSAGE_PRIORITY};
+use crate::utils::sbi_headers;
+use super::metrics::Metrics;
+
+#[derive(Clone)]
+pub struct AdmissionController {
+    config: AdmissionConfig,
+    scp_identity: String,
+    in_flight: Arc<AtomicUsize>,
+    per_nf_type: Arc<DashMap<String, usize>>,
+    metrics: Metrics,
+}
+
+pub struct AdmissionPermit {
+    controller: AdmissionController,
+    nf_type: Option<String>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.controller.in_flight.fetch_sub(1, Ordering::AcqRel);
+
+        if let Some(nf_type) = &self.nf_type {
+            if let Some(mut count) = self.controller.per_nf_type.get_mut(nf_type) {
+                *count = count.saturating_sub(1);
+            }
+            self.controller.per_nf_type.remove_if(nf_type, |_, count| *count == 0);
+        }
+    }
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig, scp_identity: String, metrics: Metrics) -> Self {
+        Self {
+            config,
+            scp_identity,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            per_nf_type: Arc::new(DashMap::new()),
+            metrics,
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    pub fn load_percent(&self) -> Option<u32> {
+        if self.config.max_in_flight == 0 {
+            return None;
+        }
+
+        Some((self.in_flight() * 100 / self.config.max_in_flight).min(100) as u32)
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        self.config.retry_after()
+    }
+
+    pub fn try_admit(&self, nf_type: Option<&str>, priority: Option<u8>) -> Result<AdmissionPermit, String> {
+        let priority = priority.unwrap_or(DEFAULT_MESSAGE_PRIORITY);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::AcqRel) + 1;
+        let mut permit = AdmissionPermit {
+            controller: self.clone(),
+            nf_type: None,
+        };
+
+        let max_in_flight = self.config.max_in_flight;
+        if max_in_flight > 0 {
+            if in_flight > max_in_flight {
+                return Err(self.reject(
+                    "capacity",
+                    nf_type,
+                    format!("SCP is at its limit of {} in-flight requests", max_in_flight),
+                ));
+            }
+
+            let load = ((in_flight - 1) * 100 / max_in_flight) as u32;
+            let cutoff = self.priority_cutoff(load);
+            if priority > cutoff {
+                return Err(self.reject(
+                    "priority",
+                    nf_type,
+                    format!(
+                        "SCP load is {}%, shedding requests with message priority above {}",
+                        load, cutoff
+                    ),
+                ));
+            }
+        }
+
+        if let Some((nf_type, limit)) = nf_type
+            .map(|nf_type| (nf_type, self.config.nf_type_limit(nf_type)))
+            .filter(|(_, limit)| *limit > 0)
+        {
+            let mut count = self.per_nf_type.entry(nf_type.to_string()).or_insert(0);
+            if *count >= limit {
+                drop(count);
+                return Err(self.reject(
+                    "nf_type_limit",
+                    Some(nf_type),
+                    format!("SCP is at its limit of {} in-flight requests towards {}", limit, nf_type),
+                ));
+            }
+            *count += 1;
+            permit.nf_type = Some(nf_type.to_string());
+        }
+
+        Ok(permit)
+    }
+
+    pub fn annotate(&self, headers: &mut HeaderMap) {
+        let Some(load) = self.load_percent() else {
+            return;
+        };
+
+        let timestamp = chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+
+        if let Ok(lci) = HeaderValue::from_str(&sbi_headers::lci(&timestamp, &self.scp_identity, load)) {
+            headers.append(sbi_headers::SBI_LCI, lci);
+        }
+
+        let threshold = self.config.shed_threshold_percent.min(99);
+        if load >= threshold {
+            let reduction = (load - threshold) * 100 / (100 - threshold);
+            let oci = sbi_headers::oci(
+                &timestamp,
+                &self.scp_identity,
+                self.config.overload_validity_seconds,
+                reduction.max(1),
+            );
+            if let Ok(oci) = HeaderValue::from_str(&oci) {
+                headers.append(sbi_headers::SBI_OCI, oci);
+            }
+        }
+    }
+
+    fn priority_cutoff(&self, load: u32) -> u8 {
+        let threshold = self.config.shed_threshold_percent.min(99);
+        let span = 100 - threshold;
+        let excess = load.saturating_sub(threshold).min(span);
+        (LOWEST_MESSAGE_PRIORITY as u32 * (span - excess) / span) as u8
+    }
+
+    fn reject(&self, reason: &'static str, nf_type: Option<&str>, detail: String) -> String {
+        tracing::warn!("Rejecting request: {}", detail);
+        self.metrics.increment_counter(
+            "scp_admission_rejected_total",
+            &[("reason", reason), ("nf_type", nf_type.unwrap_or("UNKNOWN"))],
+        );
+        detail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn controller(max_in_flight: usize, nf_type_limits: &[(&str, usize)]) -> AdmissionController {
+        let config = AdmissionConfig {
+            max_in_flight,
+            nf_type_limits: nf_type_limits
+                .iter()
+                .map(|(nf_type, limit)| (nf_type.to_string(), *limit))
+                .collect::<HashMap<_, _>>(),
+            ..AdmissionConfig::default()
+        };
+        AdmissionController::new(config, "SCP-scp.example".to_string(), Metrics::new())
+    }
+
+    #[test]
+    fn priority_cutoff_lowers_as_load_exceeds_threshold() {
+        let controller = controller(100, &[]);
+
+        assert_eq!(controller.priority_cutoff(0), LOWEST_MESSAGE_PRIORITY);
+        assert_eq!(controller.priority_cutoff(80), LOWEST_MESSAGE_PRIORITY);
+        assert_eq!(controller.priority_cutoff(90), 15);
+        assert_eq!(controller.priority_cutoff(100), 0);
+        assert_eq!(controller.priority_cutoff(150), 0);
+    }
+
+    #[test]
+    fn requests_beyond_capacity_are_rejected_and_permits_release_slots() {
+        let controller = controller(2, &[]);
+
+        let first = controller.try_admit(None, Some(0)).unwrap();
+        let _second = controller.try_admit(None, Some(0)).unwrap();
+        assert!(controller.try_admit(None, Some(0)).is_err());
+        assert_eq!(controller.in_flight(), 2);
+
+        drop(first);
+        assert!(controller.try_admit(None, Some(0)).is_ok());
+    }
+
+    #[test]
+    fn low_priority_requests_are_shed_under_load() {
+        let controller = controller(10, &[]);
+        let _permits: Vec<_> = (0..9).map(|_| controller.try_admit(None, Some(0)).unwrap()).collect();
+
+        assert!(controller.try_admit(None, Some(LOWEST_MESSAGE_PRIORITY)).is_err());
+        assert!(controller.try_admit(None, Some(0)).is_ok());
+    }
+
+    #[test]
+    fn only_nf_types_with_a_limit_are_tracked() {
+        let controller = controller(0, &[("UDM", 1)]);
+
+        let permit = controller.try_admit(Some("UDM"), None).unwrap();
+        assert!(controller.try_admit(Some("UDM"), None).is_err());
+
+        let _unlimited = controller.try_admit(Some("BOGUS"), None).unwrap();
+        assert!(!controller.per_nf_type.contains_key("BOGUS"));
+
+        drop(permit);
+        assert!(controller.per_nf_type.is_empty());
+        assert!(controller.try_admit(Some("UDM"), None).is_ok());
+    }
+
+    #[test]
+    fn overload_control_is_advertised_above_threshold() {
+        let controller = controller(10, &[]);
+        let mut headers = HeaderMap::new();
+
+        controller.annotate(&mut headers);
+        assert!(headers.contains_key(sbi_headers::SBI_LCI));
+        assert!(!headers.contains_key(sbi_headers::SBI_OCI));
+
+        let _permits: Vec<_> = (0..9).map(|_| controller.try_admit(None, Some(0)).unwrap()).collect();
+        let mut headers = HeaderMap::new();
+        controller.annotate(&mut headers);
+        let oci = headers.get(sbi_headers::SBI_OCI).unwrap().to_str().unwrap();
+        assert!(oci.contains("Overload-Reduction-Metric: 50%"));
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use url::Url;
+use crate::config::CallbackConfig;
+use crate::types::NfProfile;
+
+const DEFAULT_CALLBACK_NF_TYPES: &[(&str, &str)] = &[
+    ("Nudm_UECM_DeregistrationNotification", "AMF"),
+    ("Nudm_SDM_Notification", "AMF"),
+    ("Npcf_AMPolicyControl_UpdateNotify", "AMF"),
+    ("Npcf_UEPolicyControl_UpdateNotify", "AMF"),
+    ("Npcf_SMPolicyControl_UpdateNotify", "SMF"),
+    ("Nchf_ConvergedCharging_Notify", "SMF"),
+    ("Nsmf_PDUSession_Update", "SMF"),
+    ("Nsmf_PDUSession_StatusNotify", "SMF"),
+    ("Namf_Communication_N1N2MessageTransferFailureNotify", "SMF"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackTarget {
+    Instance(String),
+    Allowed(String),
+}
+
+#[derive(Clone)]
+pub struct CallbackRouter {
+    nf_type_mappings: Arc<HashMap<String, String>>,
+    allowed_api_roots: Arc<Vec<String>>,
+}
+
+impl CallbackRouter {
+    pub fn new(config: &CallbackConfig) -> Self {
+        let mut nf_type_mappings: HashMap<String, String> = DEFAULT_CALLBACK_NF_TYPES
+            .iter()
+            .map(|(callback_type, nf_type)| (callback_type.to_string(), nf_type.to_string()))
+            .collect();
+
+        for (callback_type, nf_type) in &config.nf_type_mappings {
+            nf_type_mappings.insert(callback_type.clone(), nf_type.to_uppercase());
+        }
+
+        Self {
+            nf_type_mappings: Arc::new(nf_type_mappings),
+            allowed_api_roots: Arc::new(config.allowed_api_roots.clone()),
+        }
+    }
+
+    pub fn resolve_nf_type(&self, callback_type: &str) -> Option<String> {
+        let mut candidate = callback_type;
+
+        loop {
+            if let Some(nf_type) = self.nf_type_mappings.get(candidate) {
+                return Some(nf_type.clone());
+            }
+
+            match candidate.rfind('_') {
+                Some(index) => candidate = &candidate[..index],
+                None => return None,
+            }
+        }
+    }
+
+    pub fn match_target(&self, api_root: &Url, nf_type: &str, profiles: &[NfProfile]) -> Option<CallbackTarget> {
+        let host = api_root.host_str()?;
+
+        if let Some(profile) = profiles.iter().find(|profile| {
+            profile.nf_type.eq_ignore_ascii_case(nf_type)
+                && (profile.fqdn.as_deref().is_some_and(|fqdn| fqdn.eq_ignore_ascii_case(host))
+                    || profile.ipv4_addresses.iter().any(|address| address == host))
+        }) {
+            return Some(CallbackTarget::Instance(profile.nf_instance_id.clone()));
+        }
+
+        let origin = api_root.origin().ascii_serialization();
+        self.allowed_api_roots
+            .iter()
+            .find(|allowed| **allowed == origin)
+            .map(|allowed| CallbackTarget::Allowed(allowed.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router(allowed_api_roots: &[&str]) -> CallbackRouter {
+        CallbackRouter::new(&CallbackConfig {
+            nf_type_mappings: HashMap::from([("Nxyz_Custom".to_string(), "nef".to_string())]),
+            allowed_api_roots: allowed_api_roots.iter().map(|root| root.to_string()).collect(),
+        })
+    }
+
+    fn profile(nf_instance_id: &str, nf_type: &str, fqdn: Option<&str>, ipv4: &[&str]) -> NfProfile {
+        NfProfile {
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type: nf_type.to_string(),
+            fqdn: fqdn.map(str::to_string),
+            ipv4_addresses: ipv4.iter().map(|address| address.to_string()).collect(),
+            ..NfProfile::default()
+        }
+    }
+
+    #[test]
+    fn resolve_nf_type_matches_the_longest_known_prefix() {
+        let router = router(&[]);
+
+        assert_eq!(router.resolve_nf_type("Nudm_SDM_Notification").as_deref(), Some("AMF"));
+        assert_eq!(router.resolve_nf_type("Nsmf_PDUSession_Update_Extra").as_deref(), Some("SMF"));
+        assert_eq!(router.resolve_nf_type("Nxyz_Custom_Notify").as_deref(), Some("NEF"));
+        assert_eq!(router.resolve_nf_type("Nbogus_Notify"), None);
+    }
+
+    #[test]
+    fn match_target_prefers_known_instances_of_the_callback_nf_type() {
+        let router = router(&[]);
+        let profiles = vec![
+            profile("smf-1", "SMF", Some("amf.example"), &[]),
+            profile("amf-1", "AMF", Some("amf.example"), &[]),
+            profile("amf-2", "AMF", None, &["10.0.0.2"]),
+        ];
+
+        let by_fqdn = Url::parse("http://AMF.example:8080/namf-callback").unwrap();
+        assert_eq!(
+            router.match_target(&by_fqdn, "AMF", &profiles),
+            Some(CallbackTarget::Instance("amf-1".to_string()))
+        );
+
+        let by_address = Url::parse("https://10.0.0.2").unwrap();
+        assert_eq!(
+            router.match_target(&by_address, "AMF", &profiles),
+            Some(CallbackTarget::Instance("amf-2".to_string()))
+        );
+    }
+
+    #[test]
+    fn match_target_rejects_unknown_roots_outside_the_allowlist() {
+        let router = router(&["http://consumer.example:8080"]);
+
+        let allowed = Url::parse("http://consumer.example:8080/callbacks").unwrap();
+        assert_eq!(
+            router.match_target(&allowed, "AMF", &[]),
+            Some(CallbackTarget::Allowed("http://consumer.example:8080".to_string()))
+        );
+
+        let other_port = Url::parse("http://consumer.example:9090").unwrap();
+        assert_eq!(router.match_target(&other_port, "AMF", &[]), None);
+
+        let unknown = Url::parse("http://attacker.example").unwrap();
+        assert_eq!(router.match_target(&unknown, "AMF", &[profile("smf-1", "SMF", Some("attacker.example"), &[])]), None);
+    }
+}
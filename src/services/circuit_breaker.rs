@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use crate::types::CircuitBreakerConfig;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "CLOSED",
+            CircuitState::Open => "OPEN",
+            CircuitState::HalfOpen => "HALF_OPEN",
+        }
+    }
+
+    pub fn as_gauge(&self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::Open => 1.0,
+            CircuitState::HalfOpen => 2.0,
+        }
+    }
+}
+
+pub type Transition = (CircuitState, CircuitState);
+
+const WINDOW_BUCKETS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct OutcomeBucket {
+    slot: u64,
+    successes: usize,
+    failures: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CircuitBreaker {
+    pub state: CircuitState,
+    pub open_until: Option<Instant>,
+    half_open_since: Option<Instant>,
+    window_epoch: Option<Instant>,
+    bucket_width: Duration,
+    buckets: [OutcomeBucket; WINDOW_BUCKETS],
+    half_open_trials: usize,
+    half_open_successes: usize,
+}
+
+impl CircuitBreaker {
+    pub fn allows_request(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        match self.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => self.open_until.is_none_or(|until| now >= until),
+            CircuitState::HalfOpen => {
+                self.half_open_trials < config.half_open_max_requests || self.half_open_is_stale(now, config)
+            }
+        }
+    }
+
+    fn half_open_is_stale(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        self.half_open_since
+            .is_some_and(|since| now.duration_since(since) >= config.open_duration())
+    }
+
+    pub fn on_request_start(&mut self, now: Instant, config: &CircuitBreakerConfig) -> Option<Transition> {
+        let transition = if self.state == CircuitState::Open && self.allows_request(now, config) {
+            Some(self.transition(CircuitState::HalfOpen, now, config))
+        } else {
+            None
+        };
+
+        if self.state == CircuitState::HalfOpen {
+            if self.half_open_trials >= config.half_open_max_requests && self.half_open_is_stale(now, config) {
+                self.half_open_trials = 0;
+                self.half_open_successes = 0;
+                self.half_open_since = Some(now);
+            }
+            self.half_open_trials += 1;
+        }
+
+        transition
+    }
+
+    pub fn record(&mut self, success: bool, now: Instant, config: &CircuitBreakerConfig) -> Option<Transition> {
+        match self.state {
+            CircuitState::Closed => {
+                self.record_outcome(success, now, config);
+
+                let (total, failure_rate) = self.failure_rate(now);
+                if total >= config.minimum_requests && failure_rate >= config.failure_rate_threshold {
+                    return Some(self.transition(CircuitState::Open, now, config));
+                }
+                None
+            }
+            CircuitState::HalfOpen => {
+                if !success {
+                    return Some(self.transition(CircuitState::Open, now, config));
+                }
+
+                self.half_open_successes += 1;
+                if self.half_open_successes >= config.half_open_max_requests {
+                    return Some(self.transition(CircuitState::Closed, now, config));
+                }
+                None
+            }
+            CircuitState::Open => None,
+        }
+    }
+
+    pub fn reset(&mut self, now: Instant, config: &CircuitBreakerConfig) -> Option<Transition> {
+        if self.state == CircuitState::Closed {
+            self.clear_window();
+            return None;
+        }
+
+        Some(self.transition(CircuitState::Closed, now, config))
+    }
+
+    pub fn restore_open(&mut self, until: Instant) {
+        self.state = CircuitState::Open;
+        self.open_until = Some(until);
+        self.half_open_since = None;
+        self.half_open_trials = 0;
+        self.half_open_successes = 0;
+    }
+
+    pub fn failure_rate(&self, now: Instant) -> (usize, f64) {
+        let Some(current) = self.slot(now) else {
+            return (0, 0.0);
+        };
+
+        let (successes, failures) = self
+            .buckets
+            .iter()
+            .filter(|bucket| current.saturating_sub(bucket.slot) < WINDOW_BUCKETS as u64)
+            .fold((0, 0), |(successes, failures), bucket| {
+                (successes + bucket.successes, failures + bucket.failures)
+            });
+
+        let total = successes + failures;
+        if total == 0 {
+            return (0, 0.0);
+        }
+
+        (total, failures as f64 / total as f64)
+    }
+
+    fn record_outcome(&mut self, success: bool, now: Instant, config: &CircuitBreakerConfig) {
+        let bucket_width = (config.window() / WINDOW_BUCKETS as u32).max(Duration::from_millis(1));
+        if self.window_epoch.is_none() || self.bucket_width != bucket_width {
+            self.clear_window();
+            self.window_epoch = Some(now);
+            self.bucket_width = bucket_width;
+        }
+
+        let Some(slot) = self.slot(now) else {
+            return;
+        };
+
+        let bucket = &mut self.buckets[slot as usize % WINDOW_BUCKETS];
+        if bucket.slot != slot {
+            *bucket = OutcomeBucket {
+                slot,
+                ..OutcomeBucket::default()
+            };
+        }
+
+        if success {
+            bucket.successes += 1;
+        } else {
+            bucket.failures += 1;
+        }
+    }
+
+    fn slot(&self, now: Instant) -> Option<u64> {
+        let epoch = self.window_epoch?;
+        let elapsed = now.saturating_duration_since(epoch).as_nanos();
+        Some((elapsed / self.bucket_width.as_nanos().max(1)) as u64)
+    }
+
+    fn clear_window(&mut self) {
+        self.window_epoch = None;
+        self.buckets = [OutcomeBucket::default(); WINDOW_BUCKETS];
+    }
+
+    fn transition(&mut self, to: CircuitState, now: Instant, config: &CircuitBreakerConfig) -> Transition {
+        let from = self.state;
+        self.state = to;
+        self.half_open_trials = 0;
+        self.half_open_successes = 0;
+
+        match to {
+            CircuitState::Open => {
+                self.open_until = Some(now + config.open_duration());
+                self.half_open_since = None;
+            }
+            CircuitState::Closed => {
+                self.open_until = None;
+                self.half_open_since = None;
+                self.clear_window();
+            }
+            CircuitState::HalfOpen => {
+                self.open_until = None;
+                self.half_open_since = Some(now);
+            }
+        }
+
+        (from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 4,
+            window_seconds: 10,
+            open_seconds: 5,
+            half_open_max_requests: 2,
+        }
+    }
+
+    fn record_all(breaker: &mut CircuitBreaker, outcomes: &[bool], now: Instant) -> Option<Transition> {
+        let config = config();
+        outcomes
+            .iter()
+            .filter_map(|success| breaker.record(*success, now, &config))
+            .last()
+    }
+
+    #[test]
+    fn opens_once_failure_rate_reaches_threshold_with_minimum_requests() {
+        let mut breaker = CircuitBreaker::default();
+        let now = Instant::now();
+
+        assert_eq!(record_all(&mut breaker, &[false, false, false], now), None);
+        assert_eq!(breaker.state, CircuitState::Closed);
+
+        assert_eq!(
+            record_all(&mut breaker, &[true], now),
+            Some((CircuitState::Closed, CircuitState::Open))
+        );
+        assert!(!breaker.allows_request(now, &config()));
+    }
+
+    #[test]
+    fn outcomes_older_than_the_window_are_forgotten() {
+        let mut breaker = CircuitBreaker::default();
+        let now = Instant::now();
+
+        record_all(&mut breaker, &[false, false, false], now);
+        assert_eq!(breaker.failure_rate(now), (3, 1.0));
+
+        let later = now + Duration::from_secs(11);
+        assert_eq!(breaker.failure_rate(later), (0, 0.0));
+        assert_eq!(record_all(&mut breaker, &[false], later), None);
+        assert_eq!(breaker.failure_rate(later), (1, 1.0));
+    }
+
+    #[test]
+    fn half_open_closes_after_enough_successful_trials() {
+        let config = config();
+        let mut breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        record_all(&mut breaker, &[false, false, false, false], now);
+
+        let reopened_at = now + Duration::from_secs(5);
+        assert!(breaker.allows_request(reopened_at, &config));
+        assert_eq!(
+            breaker.on_request_start(reopened_at, &config),
+            Some((CircuitState::Open, CircuitState::HalfOpen))
+        );
+        breaker.on_request_start(reopened_at, &config);
+        assert!(!breaker.allows_request(reopened_at, &config));
+
+        assert_eq!(breaker.record(true, reopened_at, &config), None);
+        assert_eq!(
+            breaker.record(true, reopened_at, &config),
+            Some((CircuitState::HalfOpen, CircuitState::Closed))
+        );
+        assert_eq!(breaker.failure_rate(reopened_at), (0, 0.0));
+    }
+
+    #[test]
+    fn half_open_failure_reopens_the_circuit() {
+        let config = config();
+        let mut breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        record_all(&mut breaker, &[false, false, false, false], now);
+
+        let reopened_at = now + Duration::from_secs(5);
+        breaker.on_request_start(reopened_at, &config);
+        assert_eq!(
+            breaker.record(false, reopened_at, &config),
+            Some((CircuitState::HalfOpen, CircuitState::Open))
+        );
+        assert_eq!(breaker.open_until, Some(reopened_at + config.open_duration()));
+    }
+}
@@ -0,0 +1,80 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use crate::store::StateStore;
+use crate::types::{CachedNfProfile, NfProfile};
+
+#[derive(Clone)]
+pub struct DiscoveryCache {
+    profiles: Arc<DashMap<String, CachedNfProfile>>,
+    store: Arc<dyn StateStore>,
+}
+
+impl DiscoveryCache {
+    pub fn new(store: Arc<dyn StateStore>) -> Self {
+        Self {
+            profiles: Arc::new(DashMap::new()),
+            store,
+        }
+    }
+
+    pub async fn warm(&self) {
+        match self.store.list_nf_profiles().await {
+            Ok(profiles) => {
+                let count = profiles.len();
+                for cached in profiles {
+                    self.profiles.insert(cached.profile.nf_instance_id.clone(), cached);
+                }
+                if count > 0 {
+                    tracing::info!("Loaded {} cached NF profiles from {} state store", count, self.store.backend());
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load cached NF profiles from state store: {}", e),
+        }
+    }
+
+    pub fn insert(&self, profile: NfProfile) {
+        let cached = CachedNfProfile {
+            profile,
+            cached_at: chrono::Utc::now(),
+        };
+
+        let changed = self
+            .profiles
+            .insert(cached.profile.nf_instance_id.clone(), cached.clone())
+            .is_none_or(|previous| previous.profile != cached.profile);
+
+        if changed && self.store.is_shared() {
+            let store = self.store.clone();
+            tokio::spawn(async move {
+                let nf_instance_id = cached.profile.nf_instance_id.clone();
+                if let Err(e) = store.put_nf_profile(cached).await {
+                    tracing::warn!("Failed to persist NF profile {}: {}", nf_instance_id, e);
+                }
+            });
+        }
+    }
+
+    pub fn remove(&self, nf_instance_id: &str) -> bool {
+        let removed = self.profiles.remove(nf_instance_id).is_some();
+
+        if self.store.is_shared() {
+            let store = self.store.clone();
+            let nf_instance_id = nf_instance_id.to_string();
+            tokio::spawn(async move {
+                if let Err(e) = store.remove_nf_profile(&nf_instance_id).await {
+                    tracing::warn!("Failed to remove NF profile {} from state store: {}", nf_instance_id, e);
+                }
+            });
+        }
+
+        removed
+    }
+
+    pub fn profiles(&self) -> Vec<NfProfile> {
+        self.profiles.iter().map(|entry| entry.profile.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+}
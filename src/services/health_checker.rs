@@ -0,0 +1,140 @@
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use crate::types::{AppState, HealthCheckConfig, HealthCheckKind, HealthCheckSettings, NfProfile};
+
+pub async fn run(state: AppState, settings: HealthCheckSettings) {
+    tracing::info!(
+        "Starting active health checks every {}s (default check: {})",
+        settings.interval_seconds,
+        settings.default.kind.as_str()
+    );
+
+    let mut interval = tokio::time::interval(settings.interval());
+
+    loop {
+        interval.tick().await;
+
+        let own_instance_id = state.nf_instance_id.to_string();
+        let targets: Vec<NfProfile> = state
+            .nf_profile_cache
+            .profiles()
+            .into_iter()
+            .filter(|profile| profile.nf_instance_id != own_instance_id)
+            .collect();
+
+        let mut probes = JoinSet::new();
+
+        for profile in targets {
+            let config = settings.for_nf_type(&profile.nf_type).clone();
+            let client = state.http_client.clone();
+
+            probes.spawn(async move {
+                let result = probe(&client, &profile, &config).await;
+                (profile, config, result)
+            });
+        }
+
+        while let Some(joined) = probes.join_next().await {
+            let Ok((profile, config, result)) = joined else {
+                continue;
+            };
+
+            if let Err(e) = &result {
+                tracing::debug!(
+                    "Health check ({}) failed for NF instance {}: {}",
+                    config.kind.as_str(),
+                    profile.nf_instance_id,
+                    e
+                );
+            }
+
+            state.metrics.increment_counter(
+                "scp_health_checks_total",
+                &[
+                    ("nf_type", profile.nf_type.as_str()),
+                    ("check", config.kind.as_str()),
+                    ("result", if result.is_ok() { "success" } else { "failure" }),
+                ],
+            );
+
+            state.load_balancer.record_probe_result(
+                &profile.nf_instance_id,
+                &profile.nf_type,
+                result.is_ok(),
+                config.healthy_threshold,
+                config.unhealthy_threshold,
+            );
+        }
+    }
+}
+
+async fn probe(client: &reqwest::Client, profile: &NfProfile, config: &HealthCheckConfig) -> Result<(), String> {
+    let api_root = profile
+        .api_root()
+        .ok_or_else(|| "no address in NF profile".to_string())?;
+
+    let url = url::Url::parse(&api_root).map_err(|e| format!("invalid address {}: {}", api_root, e))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("no host in {}", api_root))?
+        .to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    match config.kind {
+        HealthCheckKind::Tcp => {
+            tokio::time::timeout(config.timeout(), TcpStream::connect((host.as_str(), port)))
+                .await
+                .map_err(|_| "connect timed out".to_string())?
+                .map_err(|e| format!("connect failed: {}", e))?;
+            Ok(())
+        }
+        HealthCheckKind::Http2Ping => {
+            tokio::time::timeout(config.timeout(), http2_ping(&host, port))
+                .await
+                .map_err(|_| "HTTP/2 ping timed out".to_string())?
+        }
+        HealthCheckKind::Http => {
+            let response = client
+                .get(format!("{}{}", api_root, config.http_path))
+                .timeout(config.timeout())
+                .send()
+                .await
+                .map_err(|e| format!("HTTP check failed: {}", e))?;
+
+            if response.status().is_success() || response.status().is_redirection() {
+                Ok(())
+            } else {
+                Err(format!("HTTP check returned {}", response.status()))
+            }
+        }
+    }
+}
+
+async fn http2_ping(host: &str, port: u16) -> Result<(), String> {
+    let stream = TcpStream::connect((host, port))
+        .await
+        .map_err(|e| format!("connect failed: {}", e))?;
+
+    let (send_request, mut connection) = h2::client::handshake(stream)
+        .await
+        .map_err(|e| format!("HTTP/2 handshake failed: {}", e))?;
+
+    let mut ping_pong = connection
+        .ping_pong()
+        .ok_or_else(|| "HTTP/2 ping not available".to_string())?;
+
+    let driver = tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    let result = ping_pong
+        .ping(h2::Ping::opaque())
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("HTTP/2 ping failed: {}", e));
+
+    drop(send_request);
+    driver.abort();
+
+    result
+}
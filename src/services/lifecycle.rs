@@ -0,0 +1,235 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use crate::clients::nrf::NrfClient;
+use crate::store::StateStore;
+use crate::types::{AppState, NrfShutdownAction, ShutdownConfig};
+
+const NRF_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Default)]
+pub struct Lifecycle {
+    draining: Arc<AtomicBool>,
+    in_flight: Arc<AtomicUsize>,
+    idle: Arc<Notify>,
+}
+
+pub struct InFlightGuard {
+    lifecycle: Lifecycle,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+impl Lifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    pub fn begin_drain(&self) {
+        self.draining.store(true, Ordering::Release);
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    pub fn track_request(&self) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        InFlightGuard {
+            lifecycle: self.clone(),
+        }
+    }
+
+    pub fn admit(&self) -> Option<InFlightGuard> {
+        let guard = self.track_request();
+        if self.is_draining() {
+            return None;
+        }
+        Some(guard)
+    }
+
+    pub async fn wait_for_drain(&self, grace_period: Duration) -> bool {
+        let drained = async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+
+        tokio::time::timeout(grace_period, drained).await.is_ok()
+    }
+}
+
+pub async fn shutdown(state: AppState, config: ShutdownConfig) {
+    if let Some(registration) = &state.nrf_registration {
+        let update = async {
+            match config.nrf_action {
+                NrfShutdownAction::Undiscoverable => registration.mark_undiscoverable().await,
+                NrfShutdownAction::Deregister => registration.deregister().await,
+            }
+        };
+
+        if tokio::time::timeout(NRF_SHUTDOWN_TIMEOUT, update).await.is_err() {
+            tracing::warn!("Timed out updating NRF registration during shutdown");
+        }
+    }
+
+    state.lifecycle.begin_drain();
+
+    let in_flight = state.lifecycle.in_flight();
+    if in_flight > 0 {
+        tracing::info!(
+            "Draining {} in-flight requests (grace period {}s)",
+            in_flight,
+            config.grace_period_seconds
+        );
+    }
+
+    if !state.lifecycle.wait_for_drain(config.grace_period()).await {
+        tracing::warn!(
+            "Grace period elapsed with {} requests still in flight",
+            state.lifecycle.in_flight()
+        );
+    }
+
+    if let Some(nrf_client) = &state.nrf_client {
+        if state.state_store.is_shared() {
+            tracing::info!(
+                "Leaving NRF subscriptions in the shared {} state store to the remaining SCP instances",
+                state.state_store.backend()
+            );
+        } else {
+            let cancel = cancel_nrf_subscriptions(nrf_client, state.state_store.as_ref());
+            if tokio::time::timeout(NRF_SHUTDOWN_TIMEOUT, cancel).await.is_err() {
+                tracing::warn!("Timed out cancelling NRF subscriptions during shutdown");
+            }
+        }
+    }
+
+    tracing::info!("Shutdown sequence complete");
+}
+
+async fn cancel_nrf_subscriptions(nrf_client: &NrfClient, state_store: &dyn StateStore) -> usize {
+    let subscriptions = match state_store.list_subscriptions().await {
+        Ok(subscriptions) => subscriptions,
+        Err(e) => {
+            tracing::warn!("Failed to list NRF subscriptions: {}", e);
+            return 0;
+        }
+    };
+
+    let mut cancelled = 0;
+
+    for subscription in subscriptions {
+        if let Err(e) = nrf_client
+            .unsubscribe(&subscription.nrf_uri, &subscription.subscription_id)
+            .await
+        {
+            tracing::warn!("Failed to cancel NRF subscription {}: {:#}", subscription.subscription_id, e);
+            continue;
+        }
+
+        if let Err(e) = state_store.delete_subscription(&subscription.subscription_id).await {
+            tracing::warn!("Failed to remove NRF subscription {} from state store: {}", subscription.subscription_id, e);
+        }
+        cancelled += 1;
+    }
+
+    if cancelled > 0 {
+        tracing::info!("Cancelled {} NRF subscriptions", cancelled);
+    }
+
+    cancelled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{NrfConfig, NrfEndpointConfig};
+    use crate::store::{MemoryStateStore, SubscriptionRecord};
+    use axum::extract::Path;
+    use axum::http::StatusCode;
+    use axum::routing::delete;
+    use axum::Router;
+
+    fn subscription(subscription_id: &str, nrf_uri: &str) -> SubscriptionRecord {
+        SubscriptionRecord {
+            subscription_id: subscription_id.to_string(),
+            nrf_uri: nrf_uri.to_string(),
+            nf_type: Some("AMF".to_string()),
+            validity_time: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn admit_rejects_requests_once_draining() {
+        let lifecycle = Lifecycle::new();
+
+        let admitted = lifecycle.admit();
+        assert!(admitted.is_some());
+        assert_eq!(lifecycle.in_flight(), 1);
+
+        lifecycle.begin_drain();
+        assert!(lifecycle.admit().is_none());
+        assert_eq!(lifecycle.in_flight(), 1);
+
+        drop(admitted);
+        assert_eq!(lifecycle.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_subscriptions_are_removed_from_the_store() {
+        let router = Router::new().route(
+            "/nnrf-nfm/v1/subscriptions/:subscription_id",
+            delete(|Path(subscription_id): Path<String>| async move {
+                if subscription_id == "sub-1" {
+                    StatusCode::NO_CONTENT
+                } else {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let nrf_uri = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let nrf_client = NrfClient::new(
+            &NrfConfig {
+                endpoints: vec![NrfEndpointConfig {
+                    uri: nrf_uri.clone(),
+                    priority: 1,
+                }],
+                unhealthy_threshold: 3,
+                unhealthy_seconds: 30,
+                max_redirects: 3,
+            },
+            reqwest::Client::new(),
+        );
+        let state_store = MemoryStateStore::new();
+        state_store.put_subscription(subscription("sub-1", &nrf_uri)).await.unwrap();
+        state_store.put_subscription(subscription("sub-2", &nrf_uri)).await.unwrap();
+
+        assert_eq!(cancel_nrf_subscriptions(&nrf_client, &state_store).await, 1);
+
+        let remaining = state_store.list_subscriptions().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].subscription_id, "sub-2");
+    }
+}
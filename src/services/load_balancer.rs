@@ -1,15 +1,39 @@
 use dashmap::DashMap;
-use std::sync::Arc;
-use crate::types::NfProfile;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use crate::types::{CircuitBreakerConfig, CircuitBreakerSettings, NfProfile, OutlierDetectionConfig, StickySessionConfig};
 use rand::Rng;
+use serde::Serialize;
 use std::time::{Duration, Instant};
+use super::circuit_breaker::{CircuitBreaker, CircuitState, Transition};
+use super::metrics::Metrics;
+use crate::store::{StateStore, StoredCircuitState};
+
+const MAX_RECORDED_TRANSITIONS: usize = 100;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdminState {
+    #[default]
+    Active,
+    Draining,
+    Disabled,
+}
 
 #[derive(Clone, Debug)]
 pub struct HealthStatus {
     pub is_healthy: bool,
     pub failure_count: usize,
     pub last_failure: Option<Instant>,
-    pub circuit_open_until: Option<Instant>,
+    pub unavailable_until: Option<Instant>,
+    pub nf_type: Option<String>,
+    pub circuit: CircuitBreaker,
+    pub probe_successes: usize,
+    pub probe_failures: usize,
+    pub last_probe: Option<Instant>,
+    pub ejected_until: Option<Instant>,
+    pub ejection_count: u32,
+    pub admin_state: AdminState,
 }
 
 impl Default for HealthStatus {
@@ -18,16 +42,109 @@ impl Default for HealthStatus {
             is_healthy: true,
             failure_count: 0,
             last_failure: None,
-            circuit_open_until: None,
+            unavailable_until: None,
+            nf_type: None,
+            circuit: CircuitBreaker::default(),
+            probe_successes: 0,
+            probe_failures: 0,
+            last_probe: None,
+            ejected_until: None,
+            ejection_count: 0,
+            admin_state: AdminState::Active,
         }
     }
 }
 
+impl HealthStatus {
+    pub fn is_available(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        self.admin_state == AdminState::Active && self.passes_health_checks(now, config)
+    }
+
+    pub fn accepts_existing_sessions(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        self.admin_state != AdminState::Disabled && self.passes_health_checks(now, config)
+    }
+
+    pub fn accepts_fallback(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        self.admin_state == AdminState::Active && self.passes_protection(now, config)
+    }
+
+    fn passes_health_checks(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        self.is_healthy && self.passes_protection(now, config)
+    }
+
+    fn passes_protection(&self, now: Instant, config: &CircuitBreakerConfig) -> bool {
+        if let Some(unavailable_until) = self.unavailable_until {
+            if now < unavailable_until {
+                return false;
+            }
+        }
+
+        if self.is_ejected(now) {
+            return false;
+        }
+
+        self.circuit.allows_request(now, config)
+    }
+}
+
+impl HealthStatus {
+    pub fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.is_some_and(|until| now < until)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitTransitionRecord {
+    pub nf_instance_id: String,
+    pub nf_type: Option<String>,
+    pub from: CircuitState,
+    pub to: CircuitState,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceSnapshot {
+    pub nf_instance_id: String,
+    pub nf_type: Option<String>,
+    pub admin_state: AdminState,
+    pub is_healthy: bool,
+    pub circuit_state: CircuitState,
+    pub ejected: bool,
+    pub active_connections: usize,
+    pub consecutive_failures: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StickySessionSnapshot {
+    pub session_id: String,
+    pub nf_instance_id: String,
+    pub nf_type: String,
+    pub age_seconds: u64,
+    pub idle_seconds: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitSnapshot {
+    pub nf_instance_id: String,
+    pub nf_type: Option<String>,
+    pub state: CircuitState,
+    pub requests_in_window: usize,
+    pub failure_rate: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_remaining_ms: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 pub struct StickySession {
     pub nf_instance_id: String,
     pub nf_type: String,
     pub created_at: Instant,
+    pub last_used: Instant,
+    pub persisted_at: Option<Instant>,
 }
 
 pub struct LoadBalancer {
@@ -35,79 +152,212 @@ pub struct LoadBalancer {
     connection_counts: Arc<DashMap<String, usize>>,
     health_status: Arc<DashMap<String, HealthStatus>>,
     sticky_sessions: Arc<DashMap<String, StickySession>>,
-    failure_threshold: usize,
-    circuit_timeout: Duration,
-    session_ttl: Duration,
+    circuit_settings: Arc<RwLock<CircuitBreakerSettings>>,
+    circuit_transitions: Arc<Mutex<VecDeque<CircuitTransitionRecord>>>,
+    metrics: Metrics,
+    sticky_config: StickySessionConfig,
+    state_store: Arc<dyn StateStore>,
 }
 
 impl LoadBalancer {
-    pub fn new() -> Self {
+    pub fn new(
+        circuit_settings: CircuitBreakerSettings,
+        sticky_config: StickySessionConfig,
+        state_store: Arc<dyn StateStore>,
+        metrics: Metrics,
+    ) -> Self {
         Self {
             round_robin_index: Arc::new(DashMap::new()),
             connection_counts: Arc::new(DashMap::new()),
             health_status: Arc::new(DashMap::new()),
             sticky_sessions: Arc::new(DashMap::new()),
-            failure_threshold: 3,
-            circuit_timeout: Duration::from_secs(30),
-            session_ttl: Duration::from_secs(300),
+            circuit_settings: Arc::new(RwLock::new(circuit_settings)),
+            circuit_transitions: Arc::new(Mutex::new(VecDeque::new())),
+            metrics,
+            sticky_config,
+            state_store,
         }
     }
 
     pub fn get_sticky_session(&self, session_id: &str, nf_type: &str) -> Option<String> {
         let now = Instant::now();
 
-        if let Some(session) = self.sticky_sessions.get(session_id) {
-            if session.nf_type == nf_type {
-                let age = now.duration_since(session.created_at);
-                if age < self.session_ttl {
-                    if self.get_health_status(&session.nf_instance_id) {
-                        return Some(session.nf_instance_id.clone());
-                    } else {
-                        tracing::debug!("Sticky session for {} expired due to unhealthy instance", session_id);
-                        self.sticky_sessions.remove(session_id);
-                    }
-                } else {
-                    tracing::debug!("Sticky session for {} expired (TTL exceeded)", session_id);
-                    self.sticky_sessions.remove(session_id);
-                }
+        let nf_instance_id = {
+            let mut session = self.sticky_sessions.get_mut(session_id)?;
+
+            if session.nf_type != nf_type {
+                return None;
             }
-        }
 
-        None
+            if now.duration_since(session.last_used) >= self.sticky_config.ttl() {
+                drop(session);
+                tracing::debug!("Sticky session for {} expired (TTL exceeded)", session_id);
+                self.sticky_sessions.remove(session_id);
+                self.record_session_evictions("expired", 1);
+                return None;
+            }
+
+            session.last_used = now;
+            session.nf_instance_id.clone()
+        };
+
+        if self.get_health_status(&nf_instance_id) {
+            Some(nf_instance_id)
+        } else {
+            tracing::debug!("Sticky session for {} expired due to unhealthy instance", session_id);
+            self.sticky_sessions.remove(session_id);
+            None
+        }
     }
 
     pub fn set_sticky_session(&self, session_id: &str, nf_instance_id: &str, nf_type: &str) {
+        let now = Instant::now();
         let session = StickySession {
             nf_instance_id: nf_instance_id.to_string(),
             nf_type: nf_type.to_string(),
-            created_at: Instant::now(),
+            created_at: now,
+            last_used: now,
+            persisted_at: None,
         };
 
         self.sticky_sessions.insert(session_id.to_string(), session);
         tracing::debug!("Created sticky session: {} -> {}", session_id, nf_instance_id);
+        self.enforce_session_limit();
+    }
+
+    pub fn has_sticky_session(&self, session_id: &str) -> bool {
+        self.sticky_sessions.contains_key(session_id)
+    }
+
+    pub fn restore_sticky_session(&self, session_id: &str, nf_instance_id: &str, nf_type: &str) {
+        let now = Instant::now();
+        let session = StickySession {
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type: nf_type.to_string(),
+            created_at: now,
+            last_used: now,
+            persisted_at: Some(now),
+        };
+
+        self.sticky_sessions.insert(session_id.to_string(), session);
+        tracing::debug!("Restored sticky session from shared store: {} -> {}", session_id, nf_instance_id);
+        self.enforce_session_limit();
+    }
+
+    pub fn take_session_for_persist(&self, session_id: &str, interval: Duration) -> Option<(String, String)> {
+        let now = Instant::now();
+        let mut session = self.sticky_sessions.get_mut(session_id)?;
+
+        if session
+            .persisted_at
+            .is_some_and(|persisted_at| now.duration_since(persisted_at) < interval)
+        {
+            return None;
+        }
+
+        session.persisted_at = Some(now);
+        Some((session.nf_instance_id.clone(), session.nf_type.clone()))
+    }
+
+    fn enforce_session_limit(&self) {
+        let max_entries = self.sticky_config.max_entries;
+        if self.sticky_sessions.len() > max_entries {
+            let excess = self.sticky_sessions.len() - max_entries;
+            self.evict_least_recently_used(excess + (max_entries / 100).max(1));
+        }
+    }
+
+    fn evict_least_recently_used(&self, count: usize) {
+        let mut by_last_used: Vec<(Instant, String)> = self
+            .sticky_sessions
+            .iter()
+            .map(|entry| (entry.last_used, entry.key().clone()))
+            .collect();
+
+        let count = count.min(by_last_used.len());
+        if count == 0 {
+            return;
+        }
+
+        if count < by_last_used.len() {
+            by_last_used.select_nth_unstable(count - 1);
+        }
+
+        for (_, session_id) in by_last_used.into_iter().take(count) {
+            self.sticky_sessions.remove(&session_id);
+        }
+
+        tracing::debug!("Evicted {} least recently used sticky sessions", count);
+        self.record_session_evictions("lru", count);
     }
 
-    pub fn cleanup_expired_sessions(&self) {
+    pub fn cleanup_expired_sessions(&self) -> usize {
         let now = Instant::now();
+        let ttl = self.sticky_config.ttl();
+        let before = self.sticky_sessions.len();
 
         self.sticky_sessions.retain(|_, session| {
-            now.duration_since(session.created_at) < self.session_ttl
+            now.duration_since(session.last_used) < ttl
         });
+
+        let removed = before.saturating_sub(self.sticky_sessions.len());
+        self.record_session_evictions("expired", removed);
+        removed
+    }
+
+    pub async fn run_session_sweeper(self) {
+        let mut interval = tokio::time::interval(self.sticky_config.sweep_interval());
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let removed = self.cleanup_expired_sessions();
+            if removed > 0 {
+                tracing::debug!(
+                    "Removed {} expired sticky sessions, {} remaining",
+                    removed,
+                    self.sticky_sessions.len()
+                );
+            }
+        }
+    }
+
+    fn record_session_evictions(&self, reason: &'static str, count: usize) {
+        if count > 0 {
+            self.metrics.add_counter(
+                "scp_sticky_sessions_evicted_total",
+                &[("reason", reason)],
+                count as u64,
+            );
+        }
     }
 
     pub fn filter_healthy<'a>(&self, instances: &'a [NfProfile]) -> Vec<&'a NfProfile> {
         let now = Instant::now();
+        let settings = self.circuit_settings.read().unwrap();
+
+        instances
+            .iter()
+            .filter(|instance| {
+                if let Some(health) = self.health_status.get(&instance.nf_instance_id) {
+                    health.is_available(now, settings.for_nf_type(health.nf_type.as_deref()))
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    pub fn filter_fallback<'a>(&self, instances: &'a [NfProfile]) -> Vec<&'a NfProfile> {
+        let now = Instant::now();
+        let settings = self.circuit_settings.read().unwrap();
 
         instances
             .iter()
             .filter(|instance| {
                 if let Some(health) = self.health_status.get(&instance.nf_instance_id) {
-                    if let Some(circuit_open_until) = health.circuit_open_until {
-                        if now < circuit_open_until {
-                            return false;
-                        }
-                    }
-                    health.is_healthy
+                    health.accepts_fallback(now, settings.for_nf_type(health.nf_type.as_deref()))
                 } else {
                     true
                 }
@@ -115,6 +365,7 @@ impl LoadBalancer {
             .collect()
     }
 
+    #[allow(dead_code)]
     pub fn select_round_robin<'a>(&self, nf_type: &str, instances: &'a [NfProfile]) -> &'a NfProfile {
         if instances.is_empty() {
             panic!("Cannot select from empty instances list");
@@ -141,36 +392,27 @@ impl LoadBalancer {
         instances_to_use[selected_index]
     }
 
-    pub fn select_least_connections<'a>(&self, instances: &'a [NfProfile]) -> &'a NfProfile {
-        if instances.is_empty() {
-            panic!("Cannot select from empty instances list");
-        }
-
+    pub fn select_least_connections<'a>(&self, instances: &'a [NfProfile]) -> Option<&'a NfProfile> {
         let healthy = self.filter_healthy(instances);
         let instances_to_use: Vec<&NfProfile> = if healthy.is_empty() {
-            tracing::warn!("No healthy instances, using all instances");
-            instances.iter().collect()
+            let fallback = self.filter_fallback(instances);
+            if !fallback.is_empty() {
+                tracing::warn!("No healthy instances, using instances that only failed health checks");
+            }
+            fallback
         } else {
             healthy
         };
 
-        if instances_to_use.len() == 1 {
-            return instances_to_use[0];
-        }
-
-        let selected = instances_to_use
-            .iter()
-            .min_by_key(|instance| {
-                self.connection_counts
-                    .get(&instance.nf_instance_id)
-                    .map(|count| *count)
-                    .unwrap_or(0)
-            })
-            .expect("instances is not empty");
-
-        selected
+        instances_to_use.into_iter().min_by_key(|instance| {
+            self.connection_counts
+                .get(&instance.nf_instance_id)
+                .map(|count| *count)
+                .unwrap_or(0)
+        })
     }
 
+    #[allow(dead_code)]
     pub fn select_weighted<'a>(&self, instances: &'a [NfProfile]) -> &'a NfProfile {
         if instances.is_empty() {
             panic!("Cannot select from empty instances list");
@@ -216,11 +458,7 @@ impl LoadBalancer {
         session_id: &str,
         nf_type: &str,
         instances: &'a [NfProfile],
-    ) -> &'a NfProfile {
-        if instances.is_empty() {
-            panic!("Cannot select from empty instances list");
-        }
-
+    ) -> Option<&'a NfProfile> {
         if let Some(sticky_instance_id) = self.get_sticky_session(session_id, nf_type) {
             if let Some(instance) = instances
                 .iter()
@@ -231,17 +469,17 @@ impl LoadBalancer {
                     session_id,
                     sticky_instance_id
                 );
-                return instance;
+                return Some(instance);
             } else {
                 tracing::debug!("Sticky instance {} not in available instances", sticky_instance_id);
                 self.sticky_sessions.remove(session_id);
             }
         }
 
-        let selected = self.select_least_connections(instances);
+        let selected = self.select_least_connections(instances)?;
         self.set_sticky_session(session_id, &selected.nf_instance_id, nf_type);
 
-        selected
+        Some(selected)
     }
 
     pub fn increment_connections(&self, nf_instance_id: &str) {
@@ -261,42 +499,419 @@ impl LoadBalancer {
 
     pub fn acquire_connection(&self, nf_instance_id: String) -> ConnectionGuard {
         self.increment_connections(&nf_instance_id);
+
+        if let Some(mut entry) = self.health_status.get_mut(&nf_instance_id) {
+            let settings = self.circuit_settings.read().unwrap();
+            let config = settings.for_nf_type(entry.nf_type.as_deref());
+            if let Some(transition) = entry.circuit.on_request_start(Instant::now(), config) {
+                let nf_type = entry.nf_type.clone();
+                drop(entry);
+                self.record_transition(&nf_instance_id, nf_type, transition);
+            }
+        }
+
         ConnectionGuard {
             nf_instance_id,
             load_balancer: self.clone(),
         }
     }
 
-    pub fn mark_failure(&self, nf_instance_id: &str) {
+    pub fn mark_failure(&self, nf_instance_id: &str, nf_type: &str) {
+        self.record_outcome(nf_instance_id, nf_type, false);
+    }
+
+    pub fn mark_unavailable_for(&self, nf_instance_id: &str, duration: Duration) {
+        let mut entry = self.health_status
+            .entry(nf_instance_id.to_string())
+            .or_default();
+
+        let until = Instant::now() + duration;
+        if entry.unavailable_until.is_none_or(|current| current < until) {
+            entry.unavailable_until = Some(until);
+        }
+
+        tracing::info!(
+            "NF instance {} asked for Retry-After {:?}, excluding it until then",
+            nf_instance_id,
+            duration
+        );
+    }
+
+    pub fn mark_success(&self, nf_instance_id: &str, nf_type: &str) {
+        self.record_outcome(nf_instance_id, nf_type, true);
+    }
+
+    pub fn mark_passed_through(&self, nf_instance_id: &str, nf_type: &str, status: reqwest::StatusCode) {
+        self.record_outcome(nf_instance_id, nf_type, !status.is_server_error());
+    }
+
+    fn record_outcome(&self, nf_instance_id: &str, nf_type: &str, success: bool) {
         let now = Instant::now();
 
         let mut entry = self.health_status
             .entry(nf_instance_id.to_string())
-            .or_insert_with(HealthStatus::default);
+            .or_default();
 
-        entry.failure_count += 1;
-        entry.last_failure = Some(now);
+        entry.nf_type = Some(nf_type.to_string());
 
-        if entry.failure_count >= self.failure_threshold {
-            entry.is_healthy = false;
-            entry.circuit_open_until = Some(now + self.circuit_timeout);
-            tracing::warn!(
-                "NF instance {} marked unhealthy after {} failures, circuit open for {:?}",
+        if success {
+            entry.failure_count = 0;
+        } else {
+            entry.failure_count += 1;
+            entry.last_failure = Some(now);
+        }
+
+        let settings = self.circuit_settings.read().unwrap();
+        let transition = entry.circuit.record(success, now, settings.for_nf_type(Some(nf_type)));
+        drop(settings);
+        drop(entry);
+
+        if let Some(transition) = transition {
+            self.record_transition(nf_instance_id, Some(nf_type.to_string()), transition);
+        }
+    }
+
+    pub fn record_probe_result(
+        &self,
+        nf_instance_id: &str,
+        nf_type: &str,
+        success: bool,
+        healthy_threshold: usize,
+        unhealthy_threshold: usize,
+    ) {
+        let mut entry = self.health_status
+            .entry(nf_instance_id.to_string())
+            .or_default();
+
+        entry.nf_type = Some(nf_type.to_string());
+        entry.last_probe = Some(Instant::now());
+
+        if success {
+            entry.probe_successes += 1;
+            entry.probe_failures = 0;
+
+            if !entry.is_healthy && entry.probe_successes >= healthy_threshold {
+                entry.is_healthy = true;
+                tracing::info!(
+                    "NF instance {} marked healthy after {} successful health checks",
+                    nf_instance_id,
+                    entry.probe_successes
+                );
+            }
+        } else {
+            entry.probe_failures += 1;
+            entry.probe_successes = 0;
+
+            if entry.is_healthy && entry.probe_failures >= unhealthy_threshold {
+                entry.is_healthy = false;
+                tracing::warn!(
+                    "NF instance {} marked unhealthy after {} failed health checks",
+                    nf_instance_id,
+                    entry.probe_failures
+                );
+            }
+        }
+    }
+
+    pub fn eject(&self, nf_instance_id: &str, nf_type: &str, config: &OutlierDetectionConfig) -> Duration {
+        let mut entry = self.health_status
+            .entry(nf_instance_id.to_string())
+            .or_default();
+
+        entry.nf_type = Some(nf_type.to_string());
+        entry.ejection_count += 1;
+
+        let duration = config.ejection_duration(entry.ejection_count);
+        entry.ejected_until = Some(Instant::now() + duration);
+
+        duration
+    }
+
+    pub fn ejected_instances(&self, nf_type: &str) -> Vec<String> {
+        let now = Instant::now();
+
+        self.health_status
+            .iter()
+            .filter(|entry| entry.nf_type.as_deref() == Some(nf_type) && entry.is_ejected(now))
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
+    pub fn decay_ejections(&self) {
+        let now = Instant::now();
+
+        for mut entry in self.health_status.iter_mut() {
+            if entry.ejection_count > 0 && !entry.is_ejected(now) {
+                entry.ejection_count -= 1;
+                if entry.ejection_count == 0 {
+                    entry.ejected_until = None;
+                }
+            }
+        }
+    }
+
+    pub fn is_disabled(&self, nf_instance_id: &str) -> bool {
+        self.health_status
+            .get(nf_instance_id)
+            .is_some_and(|health| health.admin_state == AdminState::Disabled)
+    }
+
+    pub fn set_admin_state(&self, nf_instance_id: &str, admin_state: AdminState) {
+        let mut entry = self.health_status
+            .entry(nf_instance_id.to_string())
+            .or_default();
+
+        let previous = entry.admin_state;
+        entry.admin_state = admin_state;
+        drop(entry);
+
+        if admin_state == AdminState::Disabled {
+            self.sticky_sessions.retain(|_, session| session.nf_instance_id != nf_instance_id);
+        }
+
+        tracing::info!(
+            "NF instance {} admin state changed from {:?} to {:?}",
+            nf_instance_id,
+            previous,
+            admin_state
+        );
+    }
+
+    pub fn instance_snapshots(&self, known_instances: &[NfProfile]) -> Vec<InstanceSnapshot> {
+        let now = Instant::now();
+
+        let mut ids: Vec<String> = known_instances
+            .iter()
+            .map(|profile| profile.nf_instance_id.clone())
+            .chain(self.health_status.iter().map(|entry| entry.key().clone()))
+            .chain(self.connection_counts.iter().map(|entry| entry.key().clone()))
+            .collect();
+        ids.sort();
+        ids.dedup();
+
+        ids.into_iter()
+            .map(|nf_instance_id| {
+                let health = self
+                    .health_status
+                    .get(&nf_instance_id)
+                    .map(|entry| entry.value().clone())
+                    .unwrap_or_default();
+
+                let nf_type = health.nf_type.clone().or_else(|| {
+                    known_instances
+                        .iter()
+                        .find(|profile| profile.nf_instance_id == nf_instance_id)
+                        .map(|profile| profile.nf_type.clone())
+                });
+
+                InstanceSnapshot {
+                    active_connections: self
+                        .connection_counts
+                        .get(&nf_instance_id)
+                        .map(|count| *count)
+                        .unwrap_or(0),
+                    nf_instance_id,
+                    nf_type,
+                    admin_state: health.admin_state,
+                    is_healthy: health.is_healthy,
+                    circuit_state: health.circuit.state,
+                    ejected: health.is_ejected(now),
+                    consecutive_failures: health.failure_count,
+                }
+            })
+            .collect()
+    }
+
+    pub fn sticky_session_snapshots(&self) -> Vec<StickySessionSnapshot> {
+        let now = Instant::now();
+
+        self.sticky_sessions
+            .iter()
+            .map(|entry| StickySessionSnapshot {
+                session_id: entry.key().clone(),
+                nf_instance_id: entry.nf_instance_id.clone(),
+                nf_type: entry.nf_type.clone(),
+                age_seconds: now.duration_since(entry.created_at).as_secs(),
+                idle_seconds: now.duration_since(entry.last_used).as_secs(),
+            })
+            .collect()
+    }
+
+    pub fn remove_sticky_session(&self, session_id: &str) -> bool {
+        self.sticky_sessions.remove(session_id).is_some()
+    }
+
+    pub fn reset_circuit(&self, nf_instance_id: &str) -> bool {
+        let Some(mut entry) = self.health_status.get_mut(nf_instance_id) else {
+            return false;
+        };
+
+        let nf_type = entry.nf_type.clone();
+        let settings = self.circuit_settings.read().unwrap();
+        let transition = entry.circuit.reset(Instant::now(), settings.for_nf_type(nf_type.as_deref()));
+        drop(settings);
+        drop(entry);
+
+        if let Some(transition) = transition {
+            self.record_transition(nf_instance_id, nf_type, transition);
+        }
+
+        true
+    }
+
+    fn record_transition(&self, nf_instance_id: &str, nf_type: Option<String>, (from, to): Transition) {
+        match to {
+            CircuitState::Open => tracing::warn!(
+                "Circuit for NF instance {} moved from {} to {}",
                 nf_instance_id,
-                entry.failure_count,
-                self.circuit_timeout
-            );
+                from.as_str(),
+                to.as_str()
+            ),
+            _ => tracing::info!(
+                "Circuit for NF instance {} moved from {} to {}",
+                nf_instance_id,
+                from.as_str(),
+                to.as_str()
+            ),
+        }
+
+        self.metrics.increment_counter(
+            "scp_circuit_breaker_transitions_total",
+            &[
+                ("nf_type", nf_type.as_deref().unwrap_or("UNKNOWN")),
+                ("from", from.as_str()),
+                ("to", to.as_str()),
+            ],
+        );
+
+        self.persist_circuit_state(nf_instance_id, nf_type.clone(), to);
+
+        let mut transitions = self.circuit_transitions.lock().unwrap();
+        if transitions.len() >= MAX_RECORDED_TRANSITIONS {
+            transitions.pop_front();
         }
+        transitions.push_back(CircuitTransitionRecord {
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type,
+            from,
+            to,
+            at: chrono::Utc::now(),
+        });
     }
 
-    pub fn mark_success(&self, nf_instance_id: &str) {
-        if let Some(mut entry) = self.health_status.get_mut(nf_instance_id) {
-            if !entry.is_healthy {
-                tracing::info!("NF instance {} recovered", nf_instance_id);
+    fn persist_circuit_state(&self, nf_instance_id: &str, nf_type: Option<String>, state: CircuitState) {
+        if !self.state_store.is_shared() {
+            return;
+        }
+
+        let now = Instant::now();
+        let utc_now = chrono::Utc::now();
+        let open_until = self
+            .health_status
+            .get(nf_instance_id)
+            .and_then(|health| health.circuit.open_until)
+            .map(|until| utc_now + chrono::Duration::from_std(until.saturating_duration_since(now)).unwrap_or_default());
+
+        let stored = StoredCircuitState {
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type,
+            state,
+            open_until,
+            updated_at: utc_now,
+        };
+        let store = self.state_store.clone();
+
+        tokio::spawn(async move {
+            let nf_instance_id = stored.nf_instance_id.clone();
+            if let Err(e) = store.put_circuit_state(stored).await {
+                tracing::warn!("Failed to persist circuit state for {}: {}", nf_instance_id, e);
             }
-            entry.is_healthy = true;
-            entry.failure_count = 0;
-            entry.circuit_open_until = None;
+        });
+    }
+
+    pub fn restore_circuit_states(&self, states: Vec<StoredCircuitState>) -> usize {
+        let now = Instant::now();
+        let utc_now = chrono::Utc::now();
+        let mut restored = 0;
+
+        for stored in states {
+            if stored.state != CircuitState::Open {
+                continue;
+            }
+
+            let Some(remaining) = stored
+                .open_until
+                .and_then(|until| (until - utc_now).to_std().ok())
+                .filter(|remaining| !remaining.is_zero())
+            else {
+                continue;
+            };
+
+            let mut entry = self.health_status.entry(stored.nf_instance_id.clone()).or_default();
+            if entry.nf_type.is_none() {
+                entry.nf_type = stored.nf_type.clone();
+            }
+            entry.circuit.restore_open(now + remaining);
+            restored += 1;
+        }
+
+        restored
+    }
+
+    pub fn circuit_settings(&self) -> CircuitBreakerSettings {
+        self.circuit_settings.read().unwrap().clone()
+    }
+
+    pub fn set_circuit_config(&self, nf_type: Option<&str>, config: CircuitBreakerConfig) {
+        let mut settings = self.circuit_settings.write().unwrap();
+        match nf_type {
+            Some(nf_type) => {
+                settings.nf_types.insert(nf_type.to_uppercase(), config);
+            }
+            None => settings.default = config,
+        }
+    }
+
+    pub fn circuit_snapshots(&self) -> Vec<CircuitSnapshot> {
+        let now = Instant::now();
+
+        self.health_status
+            .iter()
+            .map(|entry| {
+                let health = entry.value();
+                let (requests_in_window, failure_rate) = health.circuit.failure_rate(now);
+                CircuitSnapshot {
+                    nf_instance_id: entry.key().clone(),
+                    nf_type: health.nf_type.clone(),
+                    state: health.circuit.state,
+                    requests_in_window,
+                    failure_rate,
+                    open_remaining_ms: health
+                        .circuit
+                        .open_until
+                        .map(|until| until.saturating_duration_since(now).as_millis() as u64),
+                }
+            })
+            .collect()
+    }
+
+    pub fn circuit_transitions(&self) -> Vec<CircuitTransitionRecord> {
+        self.circuit_transitions.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn export_metrics(&self) {
+        self.metrics.set_gauge("scp_sticky_sessions", &[], self.sticky_sessions.len() as f64);
+
+        for entry in self.health_status.iter() {
+            let health = entry.value();
+            self.metrics.set_gauge(
+                "scp_circuit_breaker_state",
+                &[
+                    ("nf_instance_id", entry.key().as_str()),
+                    ("nf_type", health.nf_type.as_deref().unwrap_or("UNKNOWN")),
+                ],
+                health.circuit.state.as_gauge(),
+            );
         }
     }
 
@@ -304,34 +919,36 @@ impl LoadBalancer {
         let now = Instant::now();
 
         if let Some(health) = self.health_status.get(nf_instance_id) {
-            if let Some(circuit_open_until) = health.circuit_open_until {
-                if now >= circuit_open_until {
-                    return true;
-                }
-            }
-            health.is_healthy
+            let settings = self.circuit_settings.read().unwrap();
+            health.accepts_existing_sessions(now, settings.for_nf_type(health.nf_type.as_deref()))
         } else {
             true
         }
     }
 
     pub fn get_statistics(&self) -> LoadBalancerStats {
-        let now = Instant::now();
-
         let total_connections: usize = self.connection_counts.iter().map(|entry| *entry.value()).sum();
 
         let mut healthy_instances = 0;
         let mut unhealthy_instances = 0;
         let mut circuit_open_instances = 0;
+        let mut circuit_half_open_instances = 0;
+        let mut ejected_instances = 0;
+        let now = Instant::now();
 
         for entry in self.health_status.iter() {
             let health = entry.value();
-            if let Some(circuit_open_until) = health.circuit_open_until {
-                if now < circuit_open_until {
+            if health.is_ejected(now) {
+                ejected_instances += 1;
+            }
+            match health.circuit.state {
+                CircuitState::Open => {
                     circuit_open_instances += 1;
                     unhealthy_instances += 1;
                     continue;
                 }
+                CircuitState::HalfOpen => circuit_half_open_instances += 1,
+                CircuitState::Closed => {}
             }
             if health.is_healthy {
                 healthy_instances += 1;
@@ -347,6 +964,8 @@ impl LoadBalancer {
             healthy_instances,
             unhealthy_instances,
             circuit_open_instances,
+            circuit_half_open_instances,
+            ejected_instances,
             active_sticky_sessions,
         }
     }
@@ -358,6 +977,8 @@ pub struct LoadBalancerStats {
     pub healthy_instances: usize,
     pub unhealthy_instances: usize,
     pub circuit_open_instances: usize,
+    pub circuit_half_open_instances: usize,
+    pub ejected_instances: usize,
     pub active_sticky_sessions: usize,
 }
 
@@ -368,9 +989,11 @@ impl Clone for LoadBalancer {
             connection_counts: Arc::clone(&self.connection_counts),
             health_status: Arc::clone(&self.health_status),
             sticky_sessions: Arc::clone(&self.sticky_sessions),
-            failure_threshold: self.failure_threshold,
-            circuit_timeout: self.circuit_timeout,
-            session_ttl: self.session_ttl,
+            circuit_settings: Arc::clone(&self.circuit_settings),
+            circuit_transitions: Arc::clone(&self.circuit_transitions),
+            metrics: self.metrics.clone(),
+            sticky_config: self.sticky_config.clone(),
+            state_store: Arc::clone(&self.state_store),
         }
     }
 }
@@ -385,3 +1008,156 @@ impl Drop for ConnectionGuard {
         self.load_balancer.decrement_connections(&self.nf_instance_id);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStateStore;
+
+    fn load_balancer() -> LoadBalancer {
+        let settings = CircuitBreakerSettings {
+            default: CircuitBreakerConfig {
+                failure_rate_threshold: 0.5,
+                minimum_requests: 2,
+                window_seconds: 10,
+                open_seconds: 30,
+                half_open_max_requests: 1,
+            },
+            ..CircuitBreakerSettings::default()
+        };
+        LoadBalancer::new(
+            settings,
+            StickySessionConfig::default(),
+            Arc::new(MemoryStateStore::new()),
+            Metrics::new(),
+        )
+    }
+
+    fn producers(ids: &[&str]) -> Vec<NfProfile> {
+        ids.iter()
+            .map(|id| NfProfile {
+                nf_instance_id: id.to_string(),
+                nf_type: "UDM".to_string(),
+                ..NfProfile::default()
+            })
+            .collect()
+    }
+
+    fn selected(load_balancer: &LoadBalancer, instances: &[NfProfile]) -> Option<String> {
+        load_balancer
+            .select_least_connections(instances)
+            .map(|instance| instance.nf_instance_id.clone())
+    }
+
+    #[test]
+    fn open_circuit_on_the_only_producer_stops_selection() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1"]);
+
+        load_balancer.mark_failure("udm-1", "UDM");
+        load_balancer.mark_failure("udm-1", "UDM");
+
+        assert_eq!(selected(&load_balancer, &instances), None);
+        assert!(load_balancer
+            .select_with_sticky_session("10.0.0.1", "UDM", &instances)
+            .is_none());
+    }
+
+    #[test]
+    fn exhausted_half_open_trials_are_not_selected() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1"]);
+        let config = load_balancer.circuit_settings.read().unwrap().default.clone();
+
+        let opened_at = Instant::now() - Duration::from_secs(60);
+        {
+            let mut entry = load_balancer.health_status.entry("udm-1".to_string()).or_default();
+            entry.nf_type = Some("UDM".to_string());
+            entry.circuit.record(false, opened_at, &config);
+            entry.circuit.record(false, opened_at, &config);
+        }
+        assert_eq!(selected(&load_balancer, &instances).as_deref(), Some("udm-1"));
+
+        let trial = load_balancer.acquire_connection("udm-1".to_string());
+        assert_eq!(selected(&load_balancer, &instances), None);
+
+        load_balancer.mark_success("udm-1", "UDM");
+        drop(trial);
+        assert_eq!(selected(&load_balancer, &instances).as_deref(), Some("udm-1"));
+    }
+
+    #[test]
+    fn passed_through_client_error_completes_the_half_open_trial() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1"]);
+        let config = load_balancer.circuit_settings.read().unwrap().default.clone();
+
+        let opened_at = Instant::now() - Duration::from_secs(60);
+        {
+            let mut entry = load_balancer.health_status.entry("udm-1".to_string()).or_default();
+            entry.nf_type = Some("UDM".to_string());
+            entry.circuit.record(false, opened_at, &config);
+            entry.circuit.record(false, opened_at, &config);
+        }
+
+        let trial = load_balancer.acquire_connection("udm-1".to_string());
+        assert_eq!(selected(&load_balancer, &instances), None);
+
+        load_balancer.mark_passed_through("udm-1", "UDM", reqwest::StatusCode::NOT_FOUND);
+        drop(trial);
+
+        assert_eq!(load_balancer.health_status.get("udm-1").unwrap().circuit.state, CircuitState::Closed);
+        assert_eq!(selected(&load_balancer, &instances).as_deref(), Some("udm-1"));
+    }
+
+    #[test]
+    fn passed_through_server_error_reopens_the_half_open_circuit() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1"]);
+        let config = load_balancer.circuit_settings.read().unwrap().default.clone();
+
+        let opened_at = Instant::now() - Duration::from_secs(60);
+        {
+            let mut entry = load_balancer.health_status.entry("udm-1".to_string()).or_default();
+            entry.nf_type = Some("UDM".to_string());
+            entry.circuit.record(false, opened_at, &config);
+            entry.circuit.record(false, opened_at, &config);
+        }
+
+        let trial = load_balancer.acquire_connection("udm-1".to_string());
+        load_balancer.mark_passed_through("udm-1", "UDM", reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+        drop(trial);
+
+        assert_eq!(load_balancer.health_status.get("udm-1").unwrap().circuit.state, CircuitState::Open);
+        assert_eq!(selected(&load_balancer, &instances), None);
+    }
+
+    #[test]
+    fn fallback_only_uses_instances_that_failed_health_checks() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1", "udm-2", "udm-3", "udm-4"]);
+
+        for instance in &instances {
+            load_balancer.record_probe_result(&instance.nf_instance_id, "UDM", false, 1, 1);
+        }
+        load_balancer.eject("udm-2", "UDM", &OutlierDetectionConfig::default());
+        load_balancer.mark_unavailable_for("udm-3", Duration::from_secs(30));
+        load_balancer.set_admin_state("udm-4", AdminState::Draining);
+
+        assert!(load_balancer.filter_healthy(&instances).is_empty());
+        assert_eq!(selected(&load_balancer, &instances).as_deref(), Some("udm-1"));
+
+        load_balancer.mark_failure("udm-1", "UDM");
+        load_balancer.mark_failure("udm-1", "UDM");
+        assert_eq!(selected(&load_balancer, &instances), None);
+    }
+
+    #[test]
+    fn least_connections_prefers_the_idlest_healthy_instance() {
+        let load_balancer = load_balancer();
+        let instances = producers(&["udm-1", "udm-2"]);
+
+        let _busy = load_balancer.acquire_connection("udm-1".to_string());
+        assert_eq!(selected(&load_balancer, &instances).as_deref(), Some("udm-2"));
+    }
+}
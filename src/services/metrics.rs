@@ -0,0 +1,96 @@
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct MetricKey {
+    name: &'static str,
+    labels: Vec<(&'static str, String)>,
+}
+
+impl MetricKey {
+    fn new(name: &'static str, labels: &[(&'static str, &str)]) -> Self {
+        Self {
+            name,
+            labels: labels
+                .iter()
+                .map(|(key, value)| (*key, value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn render_labels(&self) -> String {
+        if self.labels.is_empty() {
+            return String::new();
+        }
+
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, value.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+
+        format!("{{{}}}", labels.join(","))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Metrics {
+    counters: Arc<DashMap<MetricKey, u64>>,
+    gauges: Arc<DashMap<MetricKey, f64>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        self.add_counter(name, labels, 1);
+    }
+
+    pub fn add_counter(&self, name: &'static str, labels: &[(&'static str, &str)], value: u64) {
+        *self.counters.entry(MetricKey::new(name, labels)).or_insert(0) += value;
+    }
+
+    pub fn set_gauge(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
+        self.gauges.insert(MetricKey::new(name, labels), value);
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        let counters: BTreeMap<MetricKey, u64> = self
+            .counters
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        render_family(&mut output, "counter", counters.iter().map(|(key, value)| (key, value.to_string())));
+
+        let gauges: BTreeMap<MetricKey, f64> = self
+            .gauges
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        render_family(&mut output, "gauge", gauges.iter().map(|(key, value)| (key, value.to_string())));
+
+        output
+    }
+}
+
+fn render_family<'a>(
+    output: &mut String,
+    metric_type: &str,
+    samples: impl Iterator<Item = (&'a MetricKey, String)>,
+) {
+    let mut current_name = None;
+
+    for (key, value) in samples {
+        if current_name != Some(key.name) {
+            let _ = writeln!(output, "# TYPE {} {}", key.name, metric_type);
+            current_name = Some(key.name);
+        }
+        let _ = writeln!(output, "{}{} {}", key.name, key.render_labels(), value);
+    }
+}
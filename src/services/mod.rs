@@ -1 +1,14 @@
+pub mod admission;
+pub mod callback_router;
+pub mod circuit_breaker;
+pub mod discovery_cache;
+pub mod health_checker;
+pub mod lifecycle;
 pub mod load_balancer;
+pub mod metrics;
+pub mod nrf_registration;
+pub mod outlier_detection;
+pub mod rate_limiter;
+pub mod retry_budget;
+pub mod scp_router;
+pub mod session_store;
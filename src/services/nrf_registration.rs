@@ -0,0 +1,147 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::clients::nrf::{HeartbeatOutcome, NrfClient};
+use crate::types::NfProfile;
+
+#[derive(Clone)]
+pub struct NrfRegistration {
+    nrf_client: Arc<NrfClient>,
+    profile: NfProfile,
+    heartbeat_seconds: Arc<AtomicU64>,
+    registered: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+    last_heartbeat: Arc<Mutex<Option<Instant>>>,
+}
+
+impl NrfRegistration {
+    pub fn new(nrf_client: Arc<NrfClient>, profile: NfProfile, default_heartbeat_seconds: u64) -> Self {
+        Self {
+            nrf_client,
+            profile,
+            heartbeat_seconds: Arc::new(AtomicU64::new(default_heartbeat_seconds.max(1))),
+            registered: Arc::new(AtomicBool::new(false)),
+            stopped: Arc::new(AtomicBool::new(false)),
+            last_heartbeat: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn is_registered(&self) -> bool {
+        self.registered.load(Ordering::Relaxed)
+    }
+
+    pub fn last_heartbeat(&self) -> Option<Instant> {
+        *self.last_heartbeat.lock().unwrap()
+    }
+
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_seconds.load(Ordering::Relaxed))
+    }
+
+    pub async fn register(&self) -> bool {
+        match self.nrf_client.register(&self.profile).await {
+            Ok(registered_profile) => {
+                if let Some(heart_beat_timer) = registered_profile.heart_beat_timer {
+                    self.apply_heart_beat_timer(heart_beat_timer);
+                }
+                self.registered.store(true, Ordering::Relaxed);
+                self.record_heartbeat();
+                tracing::info!(
+                    "Registered with NRF, heartbeat every {}s",
+                    self.heartbeat_interval().as_secs()
+                );
+                true
+            }
+            Err(e) => {
+                self.registered.store(false, Ordering::Relaxed);
+                tracing::error!("Failed to register with NRF: {}", e);
+                false
+            }
+        }
+    }
+
+    pub async fn run_heartbeats(self) {
+        loop {
+            tokio::time::sleep(self.heartbeat_interval()).await;
+
+            if self.stopped.load(Ordering::Relaxed) {
+                return;
+            }
+
+            if !self.is_registered() {
+                tracing::info!("Retrying NRF registration");
+                self.register().await;
+                continue;
+            }
+
+            match self.nrf_client.heartbeat(&self.profile.nf_instance_id, &self.profile.nf_status).await {
+                Ok(HeartbeatOutcome::Acknowledged { heart_beat_timer }) => {
+                    tracing::debug!("Heartbeat sent to NRF");
+                    self.record_heartbeat();
+                    if let Some(heart_beat_timer) = heart_beat_timer {
+                        self.apply_heart_beat_timer(heart_beat_timer);
+                    }
+                }
+                Ok(HeartbeatOutcome::NotRegistered) => {
+                    self.registered.store(false, Ordering::Relaxed);
+                    self.register().await;
+                }
+                Err(e) => tracing::warn!("Failed to send heartbeat to NRF: {}", e),
+            }
+        }
+    }
+
+    pub async fn mark_undiscoverable(&self) -> bool {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        if !self.is_registered() {
+            return false;
+        }
+
+        match self.nrf_client.heartbeat(&self.profile.nf_instance_id, "UNDISCOVERABLE").await {
+            Ok(HeartbeatOutcome::Acknowledged { .. }) => {
+                tracing::info!("Marked NF instance {} UNDISCOVERABLE in NRF", self.profile.nf_instance_id);
+                true
+            }
+            Ok(HeartbeatOutcome::NotRegistered) => {
+                self.registered.store(false, Ordering::Relaxed);
+                false
+            }
+            Err(e) => {
+                tracing::warn!("Failed to mark NF instance UNDISCOVERABLE in NRF: {}", e);
+                false
+            }
+        }
+    }
+
+    pub async fn deregister(&self) -> bool {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        if !self.is_registered() {
+            return false;
+        }
+
+        match self.nrf_client.deregister(&self.profile.nf_instance_id).await {
+            Ok(()) => {
+                self.registered.store(false, Ordering::Relaxed);
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Failed to deregister from NRF: {}", e);
+                false
+            }
+        }
+    }
+
+    fn record_heartbeat(&self) {
+        *self.last_heartbeat.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn apply_heart_beat_timer(&self, heart_beat_timer: u32) {
+        let seconds = u64::from(heart_beat_timer.max(1));
+        let previous = self.heartbeat_seconds.swap(seconds, Ordering::Relaxed);
+        if previous != seconds {
+            tracing::info!("NRF set heartBeatTimer to {}s (was {}s)", seconds, previous);
+        }
+    }
+}
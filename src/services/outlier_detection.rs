@@ -0,0 +1,355 @@
+use dashmap::DashMap;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::types::OutlierDetectionConfig;
+use super::load_balancer::LoadBalancer;
+use super::metrics::Metrics;
+
+const MAX_LATENCY_SAMPLES: usize = 1000;
+
+#[derive(Clone, Debug, Default)]
+struct InstanceStats {
+    nf_type: String,
+    requests: u64,
+    successes: u64,
+    latencies_ms: Vec<u64>,
+}
+
+impl InstanceStats {
+    fn success_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 1.0;
+        }
+        self.successes as f64 / self.requests as f64
+    }
+
+    fn p99_ms(&self) -> Option<u64> {
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_unstable();
+        let index = ((sorted.len() as f64 * 0.99).ceil() as usize).saturating_sub(1);
+        sorted.get(index).copied()
+    }
+}
+
+#[derive(Clone)]
+pub struct OutlierDetector {
+    stats: Arc<DashMap<String, InstanceStats>>,
+    config: OutlierDetectionConfig,
+    load_balancer: LoadBalancer,
+    metrics: Metrics,
+}
+
+impl OutlierDetector {
+    pub fn new(config: OutlierDetectionConfig, load_balancer: LoadBalancer, metrics: Metrics) -> Self {
+        Self {
+            stats: Arc::new(DashMap::new()),
+            config,
+            load_balancer,
+            metrics,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn record(&self, nf_instance_id: &str, nf_type: &str, success: bool, latency: Duration) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut entry = self.stats.entry(nf_instance_id.to_string()).or_default();
+        entry.nf_type = nf_type.to_string();
+        entry.requests += 1;
+        if success {
+            entry.successes += 1;
+        }
+
+        let latency_ms = latency.as_millis() as u64;
+        if entry.latencies_ms.len() < MAX_LATENCY_SAMPLES {
+            entry.latencies_ms.push(latency_ms);
+        } else {
+            let index = rand::thread_rng().gen_range(0..entry.requests) as usize;
+            if index < MAX_LATENCY_SAMPLES {
+                entry.latencies_ms[index] = latency_ms;
+            }
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!(
+            "Starting outlier detection every {}s (max ejection {}%)",
+            self.config.interval_seconds,
+            self.config.max_ejection_percent
+        );
+
+        let mut interval = tokio::time::interval(self.config.interval());
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            self.evaluate();
+        }
+    }
+
+    fn evaluate(&self) {
+        self.load_balancer.decay_ejections();
+
+        let snapshot: Vec<(String, InstanceStats)> = self
+            .stats
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        self.stats.clear();
+
+        let mut pools: HashMap<String, Vec<(String, InstanceStats)>> = HashMap::new();
+        for (nf_instance_id, stats) in snapshot {
+            pools.entry(stats.nf_type.clone()).or_default().push((nf_instance_id, stats));
+        }
+
+        for (nf_type, hosts) in pools {
+            self.evaluate_pool(&nf_type, &hosts);
+        }
+    }
+
+    fn evaluate_pool(&self, nf_type: &str, hosts: &[(String, InstanceStats)]) {
+        let already_ejected: HashSet<String> = self.load_balancer.ejected_instances(nf_type).into_iter().collect();
+
+        let pool_size = hosts
+            .iter()
+            .map(|(nf_instance_id, _)| nf_instance_id)
+            .chain(already_ejected.iter())
+            .collect::<HashSet<_>>()
+            .len();
+
+        let max_ejected = (pool_size * self.config.max_ejection_percent as usize / 100).min(pool_size.saturating_sub(1));
+        let mut ejected_count = already_ejected.len();
+
+        let mut candidates: Vec<(&str, &'static str, f64)> = Vec::new();
+        candidates.extend(self.success_rate_outliers(hosts));
+        candidates.extend(self.latency_outliers(hosts));
+        candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+        let mut ejected_now = HashSet::new();
+
+        for (nf_instance_id, reason, _) in candidates {
+            if already_ejected.contains(nf_instance_id) || ejected_now.contains(nf_instance_id) {
+                continue;
+            }
+
+            if ejected_count >= max_ejected {
+                tracing::warn!(
+                    "Not ejecting outlier {} ({}): {} of {} {} instances already ejected",
+                    nf_instance_id,
+                    reason,
+                    ejected_count,
+                    pool_size,
+                    nf_type
+                );
+                self.metrics.increment_counter(
+                    "scp_outlier_ejections_skipped_total",
+                    &[("nf_type", nf_type), ("reason", reason)],
+                );
+                continue;
+            }
+
+            let duration = self.load_balancer.eject(nf_instance_id, nf_type, &self.config);
+            tracing::warn!(
+                "Ejecting outlier NF instance {} ({}) for {:?}",
+                nf_instance_id,
+                reason,
+                duration
+            );
+            self.metrics.increment_counter(
+                "scp_outlier_ejections_total",
+                &[("nf_type", nf_type), ("reason", reason)],
+            );
+
+            ejected_now.insert(nf_instance_id);
+            ejected_count += 1;
+        }
+
+        self.metrics.set_gauge(
+            "scp_outlier_ejected_instances",
+            &[("nf_type", nf_type)],
+            ejected_count as f64,
+        );
+    }
+
+    fn success_rate_outliers<'a>(&self, hosts: &'a [(String, InstanceStats)]) -> Vec<(&'a str, &'static str, f64)> {
+        let eligible: Vec<(&str, f64)> = hosts
+            .iter()
+            .filter(|(_, stats)| stats.requests >= self.config.success_rate_request_volume)
+            .map(|(nf_instance_id, stats)| (nf_instance_id.as_str(), stats.success_rate()))
+            .collect();
+
+        if eligible.len() < self.config.success_rate_minimum_hosts {
+            return Vec::new();
+        }
+
+        let mean = eligible.iter().map(|(_, rate)| rate).sum::<f64>() / eligible.len() as f64;
+        let variance = eligible
+            .iter()
+            .map(|(_, rate)| (rate - mean).powi(2))
+            .sum::<f64>()
+            / eligible.len() as f64;
+        let threshold = mean - self.config.success_rate_stdev_factor * variance.sqrt();
+
+        eligible
+            .into_iter()
+            .filter(|(_, rate)| *rate < threshold)
+            .map(|(nf_instance_id, rate)| (nf_instance_id, "success_rate", mean - rate))
+            .collect()
+    }
+
+    fn latency_outliers<'a>(&self, hosts: &'a [(String, InstanceStats)]) -> Vec<(&'a str, &'static str, f64)> {
+        let eligible: Vec<(&str, u64)> = hosts
+            .iter()
+            .filter(|(_, stats)| stats.latencies_ms.len() >= self.config.latency_request_volume)
+            .filter_map(|(nf_instance_id, stats)| Some((nf_instance_id.as_str(), stats.p99_ms()?)))
+            .collect();
+
+        if eligible.len() < self.config.latency_minimum_hosts {
+            return Vec::new();
+        }
+
+        let mut p99s: Vec<u64> = eligible.iter().map(|(_, p99)| *p99).collect();
+        p99s.sort_unstable();
+        let median = p99s[p99s.len() / 2].max(1) as f64;
+        let threshold = median * self.config.latency_p99_factor;
+
+        eligible
+            .into_iter()
+            .filter(|(_, p99)| *p99 as f64 > threshold)
+            .map(|(nf_instance_id, p99)| (nf_instance_id, "latency", p99 as f64 / median))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::memory::MemoryStateStore;
+
+    fn detector(max_ejection_percent: u32) -> OutlierDetector {
+        let config = OutlierDetectionConfig {
+            enabled: true,
+            max_ejection_percent,
+            success_rate_minimum_hosts: 3,
+            success_rate_request_volume: 10,
+            latency_minimum_hosts: 3,
+            latency_request_volume: 10,
+            ..OutlierDetectionConfig::default()
+        };
+        let load_balancer = LoadBalancer::new(
+            Default::default(),
+            Default::default(),
+            Arc::new(MemoryStateStore::new()),
+            Metrics::new(),
+        );
+        OutlierDetector::new(config, load_balancer, Metrics::new())
+    }
+
+    fn host(nf_instance_id: &str, requests: u64, successes: u64, latency_ms: u64) -> (String, InstanceStats) {
+        (
+            nf_instance_id.to_string(),
+            InstanceStats {
+                nf_type: "UDM".to_string(),
+                requests,
+                successes,
+                latencies_ms: vec![latency_ms; requests as usize],
+            },
+        )
+    }
+
+    #[test]
+    fn p99_picks_the_ninety_ninth_percentile_sample() {
+        let stats = InstanceStats {
+            latencies_ms: (1..=200).rev().collect(),
+            ..InstanceStats::default()
+        };
+
+        assert_eq!(stats.p99_ms(), Some(198));
+        assert_eq!(InstanceStats::default().p99_ms(), None);
+        assert_eq!(InstanceStats::default().success_rate(), 1.0);
+    }
+
+    #[test]
+    fn success_rate_outlier_falls_below_mean_minus_stdev_factor() {
+        let detector = detector(50);
+        let hosts = vec![
+            host("a", 100, 100, 10),
+            host("b", 100, 99, 10),
+            host("c", 100, 100, 10),
+            host("d", 100, 100, 10),
+            host("e", 100, 40, 10),
+        ];
+
+        let outliers = detector.success_rate_outliers(&hosts);
+
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].0, "e");
+        assert_eq!(outliers[0].1, "success_rate");
+    }
+
+    #[test]
+    fn hosts_below_request_volume_are_not_evaluated() {
+        let detector = detector(50);
+        let hosts = vec![host("a", 100, 100, 10), host("b", 100, 100, 10), host("c", 5, 0, 10)];
+
+        assert!(detector.success_rate_outliers(&hosts).is_empty());
+    }
+
+    #[test]
+    fn latency_outlier_exceeds_median_p99_by_factor() {
+        let detector = detector(50);
+        let hosts = vec![
+            host("a", 20, 20, 10),
+            host("b", 20, 20, 12),
+            host("c", 20, 20, 11),
+            host("d", 20, 20, 40),
+        ];
+
+        let outliers = detector.latency_outliers(&hosts);
+
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].0, "d");
+        assert_eq!(outliers[0].1, "latency");
+    }
+
+    #[test]
+    fn ejections_are_bounded_by_max_ejection_percent() {
+        let detector = detector(10);
+        let mut hosts: Vec<_> = ["a", "b", "c", "d", "e", "f", "g", "h"]
+            .into_iter()
+            .map(|nf_instance_id| host(nf_instance_id, 100, 100, 10))
+            .collect();
+        hosts.push(host("i", 100, 0, 10));
+        hosts.push(host("j", 100, 0, 10));
+
+        assert_eq!(detector.success_rate_outliers(&hosts).len(), 2);
+
+        detector.evaluate_pool("UDM", &hosts);
+
+        assert_eq!(detector.load_balancer.ejected_instances("UDM").len(), 1);
+    }
+
+    #[test]
+    fn ejection_duration_grows_with_count_up_to_maximum() {
+        let config = OutlierDetectionConfig {
+            base_ejection_seconds: 30,
+            max_ejection_seconds: 100,
+            ..OutlierDetectionConfig::default()
+        };
+
+        assert_eq!(config.ejection_duration(0), Duration::from_secs(30));
+        assert_eq!(config.ejection_duration(3), Duration::from_secs(90));
+        assert_eq!(config.ejection_duration(4), Duration::from_secs(100));
+    }
+}
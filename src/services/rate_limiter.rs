@@ -0,0 +1,336 @@
+use axum::http::HeaderMap;
+use dashmap::DashMap;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use crate::config::OAuth2Config;
+use crate::store::StateStore;
+use crate::types::{RateLimitConfig, RateLimitRule, RATE_LIMIT_ANY};
+use crate::utils::sbi_headers;
+use super::metrics::Metrics;
+
+const MAX_REFILL_TIME: Duration = Duration::from_secs(86_400);
+
+fn refill_time(tokens: f64, rate_per_second: f64) -> Duration {
+    Duration::try_from_secs_f64((tokens / rate_per_second).max(0.0))
+        .unwrap_or(MAX_REFILL_TIME)
+        .min(MAX_REFILL_TIME)
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+    full_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(burst: f64) -> Self {
+        let now = Instant::now();
+        Self {
+            tokens: burst,
+            updated_at: now,
+            full_at: now,
+        }
+    }
+
+    pub fn try_take(&mut self, rate_per_second: f64, burst: f64) -> bool {
+        let now = Instant::now();
+        let refill = now.duration_since(self.updated_at).as_secs_f64() * rate_per_second;
+        self.tokens = (self.tokens + refill).min(burst);
+        self.updated_at = now;
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+
+        self.full_at = now + refill_time(burst - self.tokens, rate_per_second);
+        allowed
+    }
+
+    pub fn is_full(&self) -> bool {
+        Instant::now() >= self.full_at
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimitExceeded {
+    pub detail: String,
+    pub retry_after: Duration,
+}
+
+#[derive(Deserialize)]
+struct ConsumerClaims {
+    sub: String,
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    rules: Arc<RwLock<Arc<Vec<RateLimitRule>>>>,
+    buckets: Arc<DashMap<String, TokenBucket>>,
+    state_store: Arc<dyn StateStore>,
+    token_validation: Option<Arc<(DecodingKey, Validation)>>,
+    rules_modified: Arc<Mutex<Option<SystemTime>>>,
+    metrics: Metrics,
+}
+
+impl RateLimiter {
+    pub fn new(
+        config: RateLimitConfig,
+        oauth2: &OAuth2Config,
+        state_store: Arc<dyn StateStore>,
+        metrics: Metrics,
+    ) -> Self {
+        let token_validation = (oauth2.enabled && !oauth2.secret_key.is_empty()).then(|| {
+            let mut validation = Validation::new(Algorithm::HS256);
+            if !oauth2.issuer.is_empty() {
+                validation.set_issuer(&[&oauth2.issuer]);
+            }
+            if oauth2.audience.is_empty() {
+                validation.validate_aud = false;
+            } else {
+                validation.set_audience(&oauth2.audience);
+            }
+            Arc::new((DecodingKey::from_secret(oauth2.secret_key.as_bytes()), validation))
+        });
+
+        let limiter = Self {
+            rules: Arc::new(RwLock::new(Arc::new(config.rules.clone()))),
+            config,
+            buckets: Arc::new(DashMap::new()),
+            state_store,
+            token_validation,
+            rules_modified: Arc::new(Mutex::new(None)),
+            metrics,
+        };
+
+        if let Err(e) = limiter.reload_rules_file() {
+            tracing::error!("Failed to load rate limit rules: {}", e);
+        }
+
+        limiter
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.config.shared && self.state_store.is_shared()
+    }
+
+    pub fn rules(&self) -> Vec<RateLimitRule> {
+        self.rules.read().unwrap().as_ref().clone()
+    }
+
+    pub fn set_rules(&self, rules: Vec<RateLimitRule>) {
+        tracing::info!("Applying {} rate limit rules", rules.len());
+        *self.rules.write().unwrap() = Arc::new(rules);
+    }
+
+    pub fn consumer_identity(&self, headers: &HeaderMap, addr: SocketAddr) -> String {
+        if let Some(subject) = self.token_subject(headers) {
+            return subject;
+        }
+
+        if let Some(peer) = self
+            .config
+            .peer_identity_header
+            .as_deref()
+            .and_then(|name| sbi_headers::header_str(headers, name))
+        {
+            let parameters = sbi_headers::binding_parameters(peer);
+            return parameters
+                .get("uri")
+                .or_else(|| parameters.get("subject"))
+                .cloned()
+                .unwrap_or_else(|| peer.to_string());
+        }
+
+        addr.ip().to_string()
+    }
+
+    pub async fn check(&self, consumer: &str, nf_type: &str, service_name: &str) -> Result<(), RateLimitExceeded> {
+        let rules = self.rules.read().unwrap().clone();
+        let Some(rule) = rules
+            .iter()
+            .filter_map(|rule| Some((rule.specificity(consumer, nf_type, service_name)?, rule)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, rule)| rule)
+        else {
+            return Ok(());
+        };
+
+        let target = if rule.target == RATE_LIMIT_ANY {
+            nf_type.to_string()
+        } else {
+            rule.target.to_lowercase()
+        };
+        let key = format!("{}|{}", consumer, target);
+
+        if self.take_token(&key, rule).await {
+            return Ok(());
+        }
+
+        self.metrics
+            .increment_counter("scp_rate_limited_total", &[("target", target.as_str())]);
+
+        Err(RateLimitExceeded {
+            detail: format!(
+                "Rate limit of {} requests per second towards {} exceeded for {}",
+                rule.rate_per_second, target, consumer
+            ),
+            retry_after: refill_time(1.0, rule.rate_per_second).max(Duration::from_secs(1)),
+        })
+    }
+
+    pub fn reload_rules_file(&self) -> anyhow::Result<bool> {
+        let Some(path) = &self.config.rules_file else {
+            return Ok(false);
+        };
+
+        let modified = std::fs::metadata(path)?.modified()?;
+        if *self.rules_modified.lock().unwrap() == Some(modified) {
+            return Ok(false);
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let rules: Vec<RateLimitRule> = serde_yaml::from_str(&contents)?;
+        if let Some(invalid) = rules.iter().find(|rule| !rule.is_valid()) {
+            anyhow::bail!("Invalid rate limit rule in {}: {:?}", path, invalid);
+        }
+
+        *self.rules_modified.lock().unwrap() = Some(modified);
+        tracing::info!("Loaded rate limit rules from {}", path);
+        self.set_rules(rules);
+        Ok(true)
+    }
+
+    pub async fn run_maintenance(self) {
+        let mut interval = tokio::time::interval(self.config.reload_interval());
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reload_rules_file() {
+                tracing::warn!("Failed to reload rate limit rules: {}", e);
+            }
+
+            self.buckets.retain(|_, bucket| !bucket.is_full());
+        }
+    }
+
+    async fn take_token(&self, key: &str, rule: &RateLimitRule) -> bool {
+        if self.is_shared() {
+            let shared = tokio::time::timeout(
+                self.config.shared_timeout(),
+                self.state_store
+                    .take_rate_limit_token(key, rule.rate_per_second, rule.burst()),
+            )
+            .await;
+
+            match shared {
+                Ok(Ok(allowed)) => return allowed,
+                Ok(Err(e)) => tracing::debug!("Shared rate limit unavailable, using local bucket: {}", e),
+                Err(_) => tracing::debug!("Shared rate limit timed out, using local bucket"),
+            }
+        }
+
+        self.buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket::new(rule.burst()))
+            .try_take(rule.rate_per_second, rule.burst())
+    }
+
+    fn token_subject(&self, headers: &HeaderMap) -> Option<String> {
+        let validation = self.token_validation.as_ref()?;
+        let token = sbi_headers::header_str(headers, "authorization")?.strip_prefix("Bearer ")?;
+
+        match jsonwebtoken::decode::<ConsumerClaims>(token.trim(), &validation.0, &validation.1) {
+            Ok(data) => Some(data.claims.sub),
+            Err(e) => {
+                tracing::debug!("Ignoring unverifiable access token for rate limiting: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStateStore;
+
+    #[test]
+    fn token_bucket_allows_burst_then_limits() {
+        let mut bucket = TokenBucket::new(3.0);
+
+        assert!(bucket.try_take(1.0, 3.0));
+        assert!(bucket.try_take(1.0, 3.0));
+        assert!(bucket.try_take(1.0, 3.0));
+        assert!(!bucket.try_take(1.0, 3.0));
+        assert!(!bucket.is_full());
+    }
+
+    #[test]
+    fn token_bucket_refills_at_the_configured_rate() {
+        let mut bucket = TokenBucket::new(1.0);
+
+        assert!(bucket.try_take(1000.0, 1.0));
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(bucket.try_take(1000.0, 1.0));
+    }
+
+    #[test]
+    fn token_bucket_is_full_once_refilled() {
+        let mut bucket = TokenBucket::new(2.0);
+        assert!(bucket.is_full());
+
+        bucket.try_take(1000.0, 2.0);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(bucket.is_full());
+    }
+
+    #[test]
+    fn refill_time_is_clamped_for_tiny_rates() {
+        assert_eq!(refill_time(1.0, 1e-20), MAX_REFILL_TIME);
+        assert_eq!(refill_time(1.0, 0.0), MAX_REFILL_TIME);
+        assert_eq!(refill_time(-1.0, 1.0), Duration::ZERO);
+        assert_eq!(refill_time(1.0, 4.0), Duration::from_millis(250));
+
+        let mut bucket = TokenBucket::new(1.0);
+        assert!(bucket.try_take(1e-20, 1.0));
+        assert!(!bucket.try_take(1e-20, 1.0));
+        assert!(!bucket.is_full());
+    }
+
+    #[tokio::test]
+    async fn most_specific_rule_limits_each_consumer_separately() {
+        let config = RateLimitConfig {
+            enabled: true,
+            rules: vec![
+                RateLimitRule::parse("rate=100").unwrap(),
+                RateLimitRule::parse("consumer=amf-1;target=nudm-sdm;rate=1;burst=1").unwrap(),
+            ],
+            ..RateLimitConfig::default()
+        };
+        let limiter = RateLimiter::new(
+            config,
+            &OAuth2Config::default(),
+            Arc::new(MemoryStateStore::new()),
+            Metrics::new(),
+        );
+
+        assert!(limiter.check("amf-1", "UDM", "nudm-sdm").await.is_ok());
+        let exceeded = limiter.check("amf-1", "UDM", "nudm-sdm").await.unwrap_err();
+        assert_eq!(exceeded.retry_after, Duration::from_secs(1));
+
+        assert!(limiter.check("amf-1", "UDM", "nudm-uecm").await.is_ok());
+        assert!(limiter.check("amf-2", "UDM", "nudm-sdm").await.is_ok());
+    }
+}
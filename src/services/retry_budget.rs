@@ -0,0 +1,125 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use crate::types::RetryBudgetConfig;
+use super::metrics::Metrics;
+
+#[derive(Clone)]
+pub struct RetryBudget {
+    tokens: Arc<DashMap<String, f64>>,
+    config: RetryBudgetConfig,
+    metrics: Metrics,
+}
+
+impl RetryBudget {
+    pub fn new(config: RetryBudgetConfig, metrics: Metrics) -> Self {
+        Self {
+            tokens: Arc::new(DashMap::new()),
+            config,
+            metrics,
+        }
+    }
+
+    pub fn record_request(&self, nf_type: &str) {
+        self.metrics.increment_counter("scp_requests_total", &[("nf_type", nf_type)]);
+
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut tokens = self
+            .tokens
+            .entry(nf_type.to_string())
+            .or_insert(self.config.max_tokens);
+        *tokens = (*tokens + self.config.ratio).min(self.config.max_tokens);
+    }
+
+    pub fn try_acquire(&self, nf_type: &str) -> bool {
+        if !self.config.enabled {
+            self.metrics.increment_counter("scp_retries_total", &[("nf_type", nf_type)]);
+            return true;
+        }
+
+        let mut tokens = self
+            .tokens
+            .entry(nf_type.to_string())
+            .or_insert(self.config.max_tokens);
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            self.metrics.increment_counter("scp_retries_total", &[("nf_type", nf_type)]);
+            true
+        } else {
+            tracing::warn!("Retry budget exhausted for {}, suppressing retry", nf_type);
+            self.metrics.increment_counter("scp_retry_budget_exhausted_total", &[("nf_type", nf_type)]);
+            self.metrics.increment_counter(
+                "scp_retries_suppressed_total",
+                &[("nf_type", nf_type), ("reason", "budget_exhausted")],
+            );
+            false
+        }
+    }
+
+    pub fn export_metrics(&self) {
+        for entry in self.tokens.iter() {
+            self.metrics.set_gauge(
+                "scp_retry_budget_tokens",
+                &[("nf_type", entry.key().as_str())],
+                *entry.value(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(enabled: bool) -> RetryBudget {
+        RetryBudget::new(
+            RetryBudgetConfig {
+                enabled,
+                ratio: 0.5,
+                max_tokens: 2.0,
+            },
+            Metrics::new(),
+        )
+    }
+
+    #[test]
+    fn retries_are_suppressed_once_tokens_run_out() {
+        let budget = budget(true);
+
+        assert!(budget.try_acquire("UDM"));
+        assert!(budget.try_acquire("UDM"));
+        assert!(!budget.try_acquire("UDM"));
+        assert!(budget.try_acquire("AUSF"));
+    }
+
+    #[test]
+    fn requests_refill_tokens_up_to_the_maximum() {
+        let budget = budget(true);
+
+        budget.try_acquire("UDM");
+        budget.try_acquire("UDM");
+        budget.record_request("UDM");
+        assert!(!budget.try_acquire("UDM"));
+
+        budget.record_request("UDM");
+        assert!(budget.try_acquire("UDM"));
+
+        for _ in 0..10 {
+            budget.record_request("UDM");
+        }
+        assert_eq!(*budget.tokens.get("UDM").unwrap(), 2.0);
+    }
+
+    #[test]
+    fn disabled_budget_always_allows_retries() {
+        let budget = budget(false);
+
+        for _ in 0..10 {
+            assert!(budget.try_acquire("UDM"));
+        }
+        assert!(budget.tokens.is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use crate::clients::nrf::{NfDiscoveryParams, NrfClient};
+use crate::types::{NextHopScpConfig, NfProfile, ScpRoutingConfig};
+use super::metrics::Metrics;
+
+const DEFAULT_DISCOVERED_PRIORITY: u32 = 65535;
+
+#[derive(Debug, Clone)]
+pub struct NextHop {
+    pub id: String,
+    pub uri: String,
+    pub host: String,
+    pub priority: u32,
+    pub scp_domains: Vec<String>,
+    pub address_domains: Vec<String>,
+}
+
+impl NextHop {
+    fn from_config(config: &NextHopScpConfig) -> Option<Self> {
+        let host = url::Url::parse(&config.uri).ok()?.host_str()?.to_lowercase();
+
+        Some(Self {
+            id: config.uri.clone(),
+            uri: config.uri.clone(),
+            host,
+            priority: config.priority,
+            scp_domains: config.scp_domains.clone(),
+            address_domains: config.address_domains.clone(),
+        })
+    }
+
+    fn from_profile(profile: &NfProfile) -> Option<Self> {
+        let scp_info = profile.scp_info.clone().unwrap_or_default();
+        let host = profile
+            .fqdn
+            .clone()
+            .or_else(|| profile.ipv4_addresses.first().cloned())?;
+
+        let uri = ["https", "http"]
+            .iter()
+            .find_map(|scheme| {
+                scp_info
+                    .scp_ports
+                    .get(*scheme)
+                    .map(|port| format!("{}://{}:{}", scheme, host, port))
+            })
+            .or_else(|| profile.api_root())?;
+
+        let host = url::Url::parse(&uri).ok()?.host_str()?.to_lowercase();
+
+        Some(Self {
+            id: profile.nf_instance_id.clone(),
+            uri,
+            host,
+            priority: profile.priority.unwrap_or(DEFAULT_DISCOVERED_PRIORITY),
+            scp_domains: profile.scp_domains.clone(),
+            address_domains: scp_info
+                .address_domains
+                .iter()
+                .map(|domain| domain.to_lowercase())
+                .collect(),
+        })
+    }
+
+    pub fn via_identity(&self) -> String {
+        format!("SCP-{}", self.host)
+    }
+
+    fn serves_address(&self, host: &str) -> bool {
+        self.address_domains.iter().any(|domain| {
+            let domain = domain.trim_start_matches("*.");
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct HopHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+#[derive(Clone)]
+pub struct ScpRouter {
+    local_identity: String,
+    nf_instance_id: String,
+    local_domains: Arc<HashSet<String>>,
+    static_hops: Arc<Vec<NextHop>>,
+    discovered_hops: Arc<DashMap<String, NextHop>>,
+    health: Arc<DashMap<String, HopHealth>>,
+    cursor: Arc<AtomicUsize>,
+    config: ScpRoutingConfig,
+    metrics: Metrics,
+}
+
+impl ScpRouter {
+    pub fn new(
+        config: ScpRoutingConfig,
+        local_host: &str,
+        nf_instance_id: String,
+        local_domains: &[String],
+        metrics: Metrics,
+    ) -> Self {
+        let static_hops = config
+            .next_hops
+            .iter()
+            .filter_map(|next_hop| {
+                let parsed = NextHop::from_config(next_hop);
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring next-hop SCP with invalid URI {}", next_hop.uri);
+                }
+                parsed
+            })
+            .collect();
+
+        Self {
+            local_identity: format!("SCP-{}", local_host.to_lowercase()),
+            nf_instance_id,
+            local_domains: Arc::new(local_domains.iter().cloned().collect()),
+            static_hops: Arc::new(static_hops),
+            discovered_hops: Arc::new(DashMap::new()),
+            health: Arc::new(DashMap::new()),
+            cursor: Arc::new(AtomicUsize::new(0)),
+            config,
+            metrics,
+        }
+    }
+
+    pub fn local_identity(&self) -> &str {
+        &self.local_identity
+    }
+
+    pub fn detect_loop(&self, via: &[String]) -> Option<String> {
+        if via
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(&self.local_identity))
+        {
+            return Some(format!("Request already passed through {}", self.local_identity));
+        }
+
+        if via.len() >= self.config.max_hops.max(1) {
+            return Some(format!(
+                "Request already passed through {} SCPs (limit {})",
+                via.len(),
+                self.config.max_hops
+            ));
+        }
+
+        None
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.static_hops.is_empty() || self.config.nrf_discovery
+    }
+
+    pub fn requires_next_hop(&self, profile: &NfProfile) -> bool {
+        self.is_enabled()
+            && !self.local_domains.is_empty()
+            && !profile.scp_domains.is_empty()
+            && !profile
+                .scp_domains
+                .iter()
+                .any(|domain| self.local_domains.contains(domain))
+    }
+
+    pub fn next_hops_for_producer(&self, profile: &NfProfile, via: &[String]) -> Vec<NextHop> {
+        let candidates = self
+            .all_hops()
+            .into_iter()
+            .filter(|hop| {
+                hop.scp_domains
+                    .iter()
+                    .any(|domain| profile.scp_domains.contains(domain))
+            })
+            .collect();
+
+        self.order(candidates, via)
+    }
+
+    pub fn next_hops_for_api_root(&self, api_root: &str, via: &[String]) -> Vec<NextHop> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+
+        let Some(host) = url::Url::parse(api_root)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+        else {
+            return Vec::new();
+        };
+
+        let candidates = self
+            .all_hops()
+            .into_iter()
+            .filter(|hop| hop.serves_address(&host))
+            .collect();
+
+        self.order(candidates, via)
+    }
+
+    pub fn record_success(&self, next_hop: &NextHop) {
+        self.health.remove(&next_hop.id);
+        self.metrics.increment_counter(
+            "scp_next_hop_requests_total",
+            &[("next_hop", next_hop.uri.as_str()), ("result", "success")],
+        );
+    }
+
+    pub fn record_failure(&self, next_hop: &NextHop) {
+        let mut health = self.health.entry(next_hop.id.clone()).or_default();
+        health.consecutive_failures += 1;
+
+        if health.consecutive_failures >= self.config.unhealthy_threshold.max(1) {
+            if health.unhealthy_until.is_none_or(|until| until <= Instant::now()) {
+                tracing::warn!(
+                    "Next-hop SCP {} marked unhealthy after {} consecutive failures",
+                    next_hop.uri,
+                    health.consecutive_failures
+                );
+            }
+            health.unhealthy_until = Some(Instant::now() + self.config.unhealthy_duration());
+        }
+
+        self.metrics.increment_counter(
+            "scp_next_hop_requests_total",
+            &[("next_hop", next_hop.uri.as_str()), ("result", "failure")],
+        );
+    }
+
+    pub async fn refresh_from_nrf(&self, nrf_client: &NrfClient) {
+        let params = NfDiscoveryParams {
+            target_nf_type: "SCP".to_string(),
+            requester_nf_type: Some("SCP".to_string()),
+            service_names: None,
+        };
+
+        let profiles = match nrf_client.discover(&params).await {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                tracing::warn!("Failed to discover next-hop SCPs from NRF: {}", e);
+                return;
+            }
+        };
+
+        let hops: Vec<NextHop> = profiles
+            .iter()
+            .filter(|profile| profile.nf_instance_id != self.nf_instance_id)
+            .filter(|profile| profile.nf_status == "REGISTERED")
+            .filter_map(NextHop::from_profile)
+            .filter(|hop| hop.via_identity() != self.local_identity)
+            .collect();
+
+        self.discovered_hops
+            .retain(|id, _| hops.iter().any(|hop| &hop.id == id));
+        for hop in hops {
+            self.discovered_hops.insert(hop.id.clone(), hop);
+        }
+
+        tracing::debug!("Discovered {} next-hop SCPs from NRF", self.discovered_hops.len());
+    }
+
+    pub async fn run_discovery(self, nrf_client: Arc<NrfClient>) {
+        let mut interval = tokio::time::interval(self.config.discovery_interval());
+
+        loop {
+            interval.tick().await;
+            self.refresh_from_nrf(&nrf_client).await;
+        }
+    }
+
+    fn all_hops(&self) -> Vec<NextHop> {
+        self.static_hops
+            .iter()
+            .cloned()
+            .chain(self.discovered_hops.iter().map(|entry| entry.value().clone()))
+            .collect()
+    }
+
+    fn is_healthy(&self, next_hop: &NextHop) -> bool {
+        self.health
+            .get(&next_hop.id)
+            .is_none_or(|health| health.unhealthy_until.is_none_or(|until| until <= Instant::now()))
+    }
+
+    fn order(&self, candidates: Vec<NextHop>, via: &[String]) -> Vec<NextHop> {
+        let mut hops: Vec<(bool, NextHop)> = candidates
+            .into_iter()
+            .filter(|hop| {
+                let identity = hop.via_identity();
+                let loops = identity == self.local_identity
+                    || via.iter().any(|entry| entry.eq_ignore_ascii_case(&identity));
+                if loops {
+                    tracing::debug!("Skipping next-hop SCP {} already on the request path", hop.uri);
+                }
+                !loops
+            })
+            .map(|hop| (self.is_healthy(&hop), hop))
+            .collect();
+
+        hops.sort_by_key(|(healthy, hop)| (!healthy, hop.priority));
+
+        let preferred = hops
+            .iter()
+            .take_while(|(healthy, hop)| *healthy == hops[0].0 && hop.priority == hops[0].1.priority)
+            .count();
+        if preferred > 1 {
+            let offset = self.cursor.fetch_add(1, Ordering::Relaxed) % preferred;
+            hops[..preferred].rotate_left(offset);
+        }
+
+        hops.into_iter().map(|(_, hop)| hop).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router(max_hops: usize) -> ScpRouter {
+        let config = ScpRoutingConfig {
+            max_hops,
+            ..ScpRoutingConfig::default()
+        };
+        ScpRouter::new(config, "SCP-A.example", "scp-a".to_string(), &[], Metrics::new())
+    }
+
+    #[test]
+    fn loop_is_detected_when_via_contains_local_identity() {
+        let router = router(8);
+
+        assert_eq!(router.local_identity(), "SCP-scp-a.example");
+        assert!(router.detect_loop(&["SCP-scp-b.example".to_string()]).is_none());
+        assert!(router
+            .detect_loop(&["SCP-scp-b.example".to_string(), "scp-SCP-A.EXAMPLE".to_string()])
+            .is_some());
+    }
+
+    #[test]
+    fn loop_is_detected_when_hop_limit_is_reached() {
+        let router = router(2);
+        let via = vec!["SCP-scp-b.example".to_string()];
+
+        assert!(router.detect_loop(&via).is_none());
+        assert!(router
+            .detect_loop(&[via[0].clone(), "SCP-scp-c.example".to_string()])
+            .is_some());
+    }
+}
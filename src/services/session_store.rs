@@ -0,0 +1,283 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::store::{StateStore, StoredBinding, StoredStickySession};
+use crate::types::StateStoreConfig;
+use super::metrics::Metrics;
+
+const MIN_BINDING_PATH_SEGMENTS: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub nf_instance_id: String,
+    pub nf_type: String,
+    pub nf_set_id: Option<String>,
+    pub binding_level: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedBinding {
+    binding: Option<Binding>,
+    expires_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct SessionStore {
+    store: Arc<dyn StateStore>,
+    bindings: Arc<DashMap<String, CachedBinding>>,
+    config: StateStoreConfig,
+    sticky_ttl: Duration,
+    metrics: Metrics,
+}
+
+impl SessionStore {
+    pub fn new(store: Arc<dyn StateStore>, config: StateStoreConfig, sticky_ttl: Duration, metrics: Metrics) -> Self {
+        Self {
+            store,
+            bindings: Arc::new(DashMap::new()),
+            config,
+            sticky_ttl,
+            metrics,
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.store.is_shared()
+    }
+
+    pub fn write_interval(&self) -> Duration {
+        self.config.write_interval()
+    }
+
+    pub async fn load_sticky_session(&self, session_id: &str, nf_type: &str) -> Option<String> {
+        if !self.store.is_shared() {
+            return None;
+        }
+
+        match self.store.get_sticky_session(session_id).await {
+            Ok(session) => {
+                let session = session.filter(|session| session.nf_type == nf_type);
+                self.record_operation("load_sticky_session", if session.is_some() { "hit" } else { "miss" });
+                session.map(|session| session.nf_instance_id)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load sticky session {} from state store: {}", session_id, e);
+                self.record_operation("load_sticky_session", "error");
+                None
+            }
+        }
+    }
+
+    pub fn persist_sticky_session(&self, session_id: &str, nf_instance_id: &str, nf_type: &str) {
+        if !self.store.is_shared() {
+            return;
+        }
+
+        let session = StoredStickySession {
+            session_id: session_id.to_string(),
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type: nf_type.to_string(),
+            expires_at: expires_after(self.sticky_ttl),
+        };
+        let store = self.clone();
+
+        tokio::spawn(async move {
+            let session_id = session.session_id.clone();
+            match store.store.put_sticky_session(session).await {
+                Ok(()) => store.record_operation("persist_sticky_session", "ok"),
+                Err(e) => {
+                    tracing::warn!("Failed to persist sticky session {}: {}", session_id, e);
+                    store.record_operation("persist_sticky_session", "error");
+                }
+            }
+        });
+    }
+
+    pub fn remove_sticky_session(&self, session_id: &str) {
+        if !self.store.is_shared() {
+            return;
+        }
+
+        let session_id = session_id.to_string();
+        let store = self.clone();
+
+        tokio::spawn(async move {
+            match store.store.delete_sticky_session(&session_id).await {
+                Ok(_) => store.record_operation("remove_sticky_session", "ok"),
+                Err(e) => {
+                    tracing::warn!("Failed to remove sticky session {} from state store: {}", session_id, e);
+                    store.record_operation("remove_sticky_session", "error");
+                }
+            }
+        });
+    }
+
+    pub async fn resolve_binding(&self, path: &str) -> Option<Binding> {
+        let candidates = binding_candidates(path);
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let now = Instant::now();
+
+        for candidate in &candidates {
+            if let Some(entry) = self.bindings.get(candidate) {
+                if entry.expires_at > now && entry.binding.is_some() {
+                    return entry.binding.clone();
+                }
+            }
+        }
+
+        if !self.store.is_shared()
+            || self
+                .bindings
+                .get(path)
+                .is_some_and(|entry| entry.binding.is_none() && entry.expires_at > now)
+        {
+            return None;
+        }
+
+        let stored = match self.store.find_bindings(&candidates).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::warn!("Failed to look up binding for {} in state store: {}", path, e);
+                self.record_operation("resolve_binding", "error");
+                return None;
+            }
+        };
+
+        let Some(stored) = stored
+            .into_iter()
+            .max_by_key(|binding| binding.resource_path.len())
+        else {
+            self.record_operation("resolve_binding", "miss");
+            self.bindings.insert(
+                path.to_string(),
+                CachedBinding {
+                    binding: None,
+                    expires_at: now + self.config.negative_cache(),
+                },
+            );
+            return None;
+        };
+
+        self.record_operation("resolve_binding", "hit");
+
+        let remaining = (stored.expires_at - chrono::Utc::now()).to_std().unwrap_or_default();
+        let binding = Binding {
+            nf_instance_id: stored.nf_instance_id,
+            nf_type: stored.nf_type,
+            nf_set_id: stored.nf_set_id,
+            binding_level: stored.binding_level,
+        };
+
+        self.bindings.insert(
+            stored.resource_path,
+            CachedBinding {
+                binding: Some(binding.clone()),
+                expires_at: now + remaining.min(self.config.binding_ttl()),
+            },
+        );
+
+        Some(binding)
+    }
+
+    pub fn record_binding(&self, resource_path: &str, binding: Binding) {
+        let resource_path = resource_path.trim_end_matches('/').to_string();
+
+        tracing::debug!(
+            "Recording binding {} -> {} ({})",
+            resource_path,
+            binding.nf_instance_id,
+            binding.binding_level.as_deref().unwrap_or("nfinstance")
+        );
+
+        self.bindings.insert(
+            resource_path.clone(),
+            CachedBinding {
+                binding: Some(binding.clone()),
+                expires_at: Instant::now() + self.config.binding_ttl(),
+            },
+        );
+
+        if !self.store.is_shared() {
+            return;
+        }
+
+        let stored = StoredBinding {
+            resource_path,
+            nf_instance_id: binding.nf_instance_id,
+            nf_type: binding.nf_type,
+            nf_set_id: binding.nf_set_id,
+            binding_level: binding.binding_level,
+            expires_at: expires_after(self.config.binding_ttl()),
+        };
+        let store = self.clone();
+
+        tokio::spawn(async move {
+            let resource_path = stored.resource_path.clone();
+            match store.store.put_binding(stored).await {
+                Ok(()) => store.record_operation("record_binding", "ok"),
+                Err(e) => {
+                    tracing::warn!("Failed to persist binding for {}: {}", resource_path, e);
+                    store.record_operation("record_binding", "error");
+                }
+            }
+        });
+    }
+
+    pub fn cleanup_expired_bindings(&self) -> usize {
+        let now = Instant::now();
+        let before = self.bindings.len();
+        self.bindings.retain(|_, entry| entry.expires_at > now);
+        before.saturating_sub(self.bindings.len())
+    }
+
+    pub async fn run_sweeper(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval.max(Duration::from_secs(1)));
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let removed = self.cleanup_expired_bindings();
+            if removed > 0 {
+                tracing::debug!(
+                    "Removed {} expired cached bindings, {} remaining",
+                    removed,
+                    self.bindings.len()
+                );
+            }
+
+            match self.store.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::debug!("Purged {} expired entries from {} state store", purged, self.store.backend()),
+                Err(e) => tracing::warn!("Failed to purge expired state store entries: {}", e),
+            }
+        }
+    }
+
+    fn record_operation(&self, operation: &str, result: &str) {
+        self.metrics.increment_counter(
+            "scp_state_store_operations_total",
+            &[("backend", self.store.backend()), ("operation", operation), ("result", result)],
+        );
+    }
+}
+
+fn expires_after(ttl: Duration) -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now() + chrono::Duration::from_std(ttl).unwrap_or_else(|_| chrono::Duration::days(1))
+}
+
+fn binding_candidates(path: &str) -> Vec<String> {
+    let segments: Vec<&str> = path
+        .trim_end_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    (MIN_BINDING_PATH_SEGMENTS..=segments.len())
+        .rev()
+        .map(|length| format!("/{}", segments[..length].join("/")))
+        .collect()
+}
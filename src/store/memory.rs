@@ -0,0 +1,221 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use dashmap::DashMap;
+use std::sync::Arc;
+use crate::services::rate_limiter::TokenBucket;
+use crate::types::CachedNfProfile;
+use super::{StateStore, StoredBinding, StoredCircuitState, StoredStickySession, SubscriptionRecord};
+
+#[derive(Clone, Default)]
+pub struct MemoryStateStore {
+    sticky_sessions: Arc<DashMap<String, StoredStickySession>>,
+    bindings: Arc<DashMap<String, StoredBinding>>,
+    circuit_states: Arc<DashMap<String, StoredCircuitState>>,
+    nf_profiles: Arc<DashMap<String, CachedNfProfile>>,
+    subscriptions: Arc<DashMap<String, SubscriptionRecord>>,
+    rate_limits: Arc<DashMap<String, TokenBucket>>,
+}
+
+impl MemoryStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_sticky_session(&self, session_id: &str) -> Result<Option<StoredStickySession>> {
+        let now = Utc::now();
+        Ok(self
+            .sticky_sessions
+            .get(session_id)
+            .filter(|session| session.expires_at > now)
+            .map(|session| session.clone()))
+    }
+
+    async fn put_sticky_session(&self, session: StoredStickySession) -> Result<()> {
+        self.sticky_sessions.insert(session.session_id.clone(), session);
+        Ok(())
+    }
+
+    async fn delete_sticky_session(&self, session_id: &str) -> Result<bool> {
+        Ok(self.sticky_sessions.remove(session_id).is_some())
+    }
+
+    async fn find_bindings(&self, resource_paths: &[String]) -> Result<Vec<StoredBinding>> {
+        let now = Utc::now();
+        Ok(resource_paths
+            .iter()
+            .filter_map(|path| self.bindings.get(path))
+            .filter(|binding| binding.expires_at > now)
+            .map(|binding| binding.clone())
+            .collect())
+    }
+
+    async fn put_binding(&self, binding: StoredBinding) -> Result<()> {
+        self.bindings.insert(binding.resource_path.clone(), binding);
+        Ok(())
+    }
+
+    async fn get_circuit_state(&self, nf_instance_id: &str) -> Result<Option<StoredCircuitState>> {
+        Ok(self.circuit_states.get(nf_instance_id).map(|state| state.clone()))
+    }
+
+    async fn put_circuit_state(&self, state: StoredCircuitState) -> Result<()> {
+        self.circuit_states.insert(state.nf_instance_id.clone(), state);
+        Ok(())
+    }
+
+    async fn list_circuit_states(&self) -> Result<Vec<StoredCircuitState>> {
+        Ok(self.circuit_states.iter().map(|entry| entry.value().clone()).collect())
+    }
+
+    async fn get_nf_profile(&self, nf_instance_id: &str) -> Result<Option<CachedNfProfile>> {
+        Ok(self.nf_profiles.get(nf_instance_id).map(|profile| profile.clone()))
+    }
+
+    async fn put_nf_profile(&self, profile: CachedNfProfile) -> Result<()> {
+        self.nf_profiles.insert(profile.profile.nf_instance_id.clone(), profile);
+        Ok(())
+    }
+
+    async fn remove_nf_profile(&self, nf_instance_id: &str) -> Result<bool> {
+        Ok(self.nf_profiles.remove(nf_instance_id).is_some())
+    }
+
+    async fn list_nf_profiles(&self) -> Result<Vec<CachedNfProfile>> {
+        Ok(self.nf_profiles.iter().map(|entry| entry.value().clone()).collect())
+    }
+
+    async fn get_subscription(&self, subscription_id: &str) -> Result<Option<SubscriptionRecord>> {
+        Ok(self.subscriptions.get(subscription_id).map(|subscription| subscription.clone()))
+    }
+
+    async fn put_subscription(&self, subscription: SubscriptionRecord) -> Result<()> {
+        self.subscriptions.insert(subscription.subscription_id.clone(), subscription);
+        Ok(())
+    }
+
+    async fn delete_subscription(&self, subscription_id: &str) -> Result<bool> {
+        Ok(self.subscriptions.remove(subscription_id).is_some())
+    }
+
+    async fn list_subscriptions(&self) -> Result<Vec<SubscriptionRecord>> {
+        Ok(self.subscriptions.iter().map(|entry| entry.value().clone()).collect())
+    }
+
+    async fn take_rate_limit_token(&self, key: &str, rate_per_second: f64, burst: f64) -> Result<bool> {
+        Ok(self
+            .rate_limits
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket::new(burst))
+            .try_take(rate_per_second, burst))
+    }
+
+    async fn purge_expired(&self) -> Result<usize> {
+        let now = Utc::now();
+        let before = self.sticky_sessions.len()
+            + self.bindings.len()
+            + self.subscriptions.len()
+            + self.rate_limits.len();
+
+        self.sticky_sessions.retain(|_, session| session.expires_at > now);
+        self.bindings.retain(|_, binding| binding.expires_at > now);
+        self.subscriptions
+            .retain(|_, subscription| subscription.validity_time.is_none_or(|validity| validity > now));
+        self.rate_limits.retain(|_, bucket| !bucket.is_full());
+
+        let after = self.sticky_sessions.len()
+            + self.bindings.len()
+            + self.subscriptions.len()
+            + self.rate_limits.len();
+        Ok(before.saturating_sub(after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::circuit_breaker::CircuitState;
+    use crate::store::SubscriptionRecord;
+    use crate::types::NfProfile;
+
+    #[tokio::test]
+    async fn circuit_states_and_nf_profiles_round_trip() {
+        let store = MemoryStateStore::new();
+        let now = Utc::now();
+
+        store
+            .put_circuit_state(StoredCircuitState {
+                nf_instance_id: "udm-1".to_string(),
+                nf_type: Some("UDM".to_string()),
+                state: CircuitState::Open,
+                open_until: Some(now),
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+        let circuit = store.get_circuit_state("udm-1").await.unwrap().unwrap();
+        assert_eq!(circuit.state, CircuitState::Open);
+        assert!(store.get_circuit_state("udm-2").await.unwrap().is_none());
+
+        store
+            .put_nf_profile(CachedNfProfile {
+                profile: NfProfile {
+                    nf_instance_id: "udm-1".to_string(),
+                    nf_type: "UDM".to_string(),
+                    ..NfProfile::default()
+                },
+                cached_at: now,
+            })
+            .await
+            .unwrap();
+        assert_eq!(store.get_nf_profile("udm-1").await.unwrap().unwrap().profile.nf_type, "UDM");
+        assert_eq!(store.list_nf_profiles().await.unwrap().len(), 1);
+        assert!(store.remove_nf_profile("udm-1").await.unwrap());
+        assert!(store.get_nf_profile("udm-1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn expired_subscriptions_are_purged() {
+        let store = MemoryStateStore::new();
+        let now = Utc::now();
+        let subscription = |subscription_id: &str, validity_time| SubscriptionRecord {
+            subscription_id: subscription_id.to_string(),
+            nrf_uri: "http://nrf.example".to_string(),
+            nf_type: Some("UDM".to_string()),
+            validity_time,
+            created_at: now,
+        };
+
+        store
+            .put_subscription(subscription("current", Some(now + chrono::Duration::hours(1))))
+            .await
+            .unwrap();
+        store
+            .put_subscription(subscription("expired", Some(now - chrono::Duration::seconds(1))))
+            .await
+            .unwrap();
+        assert_eq!(store.list_subscriptions().await.unwrap().len(), 2);
+
+        assert_eq!(store.purge_expired().await.unwrap(), 1);
+        assert!(store.get_subscription("current").await.unwrap().is_some());
+        assert!(store.get_subscription("expired").await.unwrap().is_none());
+
+        assert!(store.delete_subscription("current").await.unwrap());
+        assert!(store.list_subscriptions().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+pub mod memory;
+#[cfg(feature = "mongodb")]
+pub mod mongo;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::services::circuit_breaker::CircuitState;
+use crate::types::CachedNfProfile;
+
+pub use memory::MemoryStateStore;
+#[cfg(feature = "mongodb")]
+pub use mongo::MongoStateStore;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredStickySession {
+    pub session_id: String,
+    pub nf_instance_id: String,
+    pub nf_type: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredBinding {
+    pub resource_path: String,
+    pub nf_instance_id: String,
+    pub nf_type: String,
+    pub nf_set_id: Option<String>,
+    pub binding_level: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredCircuitState {
+    pub nf_instance_id: String,
+    pub nf_type: Option<String>,
+    pub state: CircuitState,
+    pub open_until: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubscriptionRecord {
+    pub subscription_id: String,
+    pub nrf_uri: String,
+    pub nf_type: Option<String>,
+    pub validity_time: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    fn backend(&self) -> &'static str;
+
+    fn is_shared(&self) -> bool;
+
+    async fn ping(&self) -> Result<()>;
+
+    async fn get_sticky_session(&self, session_id: &str) -> Result<Option<StoredStickySession>>;
+
+    async fn put_sticky_session(&self, session: StoredStickySession) -> Result<()>;
+
+    async fn delete_sticky_session(&self, session_id: &str) -> Result<bool>;
+
+    async fn find_bindings(&self, resource_paths: &[String]) -> Result<Vec<StoredBinding>>;
+
+    async fn put_binding(&self, binding: StoredBinding) -> Result<()>;
+
+    #[allow(dead_code)]
+    async fn get_circuit_state(&self, nf_instance_id: &str) -> Result<Option<StoredCircuitState>>;
+
+    async fn put_circuit_state(&self, state: StoredCircuitState) -> Result<()>;
+
+    async fn list_circuit_states(&self) -> Result<Vec<StoredCircuitState>>;
+
+    #[allow(dead_code)]
+    async fn get_nf_profile(&self, nf_instance_id: &str) -> Result<Option<CachedNfProfile>>;
+
+    async fn put_nf_profile(&self, profile: CachedNfProfile) -> Result<()>;
+
+    async fn remove_nf_profile(&self, nf_instance_id: &str) -> Result<bool>;
+
+    async fn list_nf_profiles(&self) -> Result<Vec<CachedNfProfile>>;
+
+    #[allow(dead_code)]
+    async fn get_subscription(&self, subscription_id: &str) -> Result<Option<SubscriptionRecord>>;
+
+    #[allow(dead_code)]
+    async fn put_subscription(&self, subscription: SubscriptionRecord) -> Result<()>;
+
+    async fn delete_subscription(&self, subscription_id: &str) -> Result<bool>;
+
+    async fn list_subscriptions(&self) -> Result<Vec<SubscriptionRecord>>;
+
+    async fn take_rate_limit_token(&self, key: &str, rate_per_second: f64, burst: f64) -> Result<bool>;
+
+    async fn purge_expired(&self) -> Result<usize>;
+}
@@ -0,0 +1,510 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use mongodb::bson::{doc, DateTime, Document};
+use mongodb::options::{IndexOptions, ReturnDocument};
+use mongodb::{Collection, Database, IndexModel};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::services::circuit_breaker::CircuitState;
+use crate::types::{CachedNfProfile, NfProfile};
+use super::{StateStore, StoredBinding, StoredCircuitState, StoredStickySession, SubscriptionRecord};
+
+pub const STICKY_SESSIONS_COLLECTION: &str = "sticky_sessions";
+pub const BINDINGS_COLLECTION: &str = "bindings";
+pub const CIRCUIT_STATES_COLLECTION: &str = "circuit_states";
+pub const NF_PROFILES_COLLECTION: &str = "nf_profiles";
+pub const SUBSCRIPTIONS_COLLECTION: &str = "subscriptions";
+pub const RATE_LIMITS_COLLECTION: &str = "rate_limits";
+
+const RATE_LIMIT_IDLE_MS: i64 = 60_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StickySessionDocument {
+    #[serde(rename = "_id")]
+    session_id: String,
+    nf_instance_id: String,
+    nf_type: String,
+    updated_at: DateTime,
+    expires_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BindingDocument {
+    #[serde(rename = "_id")]
+    resource_path: String,
+    nf_instance_id: String,
+    nf_type: String,
+    nf_set_id: Option<String>,
+    binding_level: Option<String>,
+    updated_at: DateTime,
+    expires_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CircuitStateDocument {
+    #[serde(rename = "_id")]
+    nf_instance_id: String,
+    nf_type: Option<String>,
+    state: CircuitState,
+    open_until: Option<DateTime>,
+    updated_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NfProfileDocument {
+    #[serde(rename = "_id")]
+    nf_instance_id: String,
+    profile: NfProfile,
+    cached_at: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SubscriptionDocument {
+    #[serde(rename = "_id")]
+    subscription_id: String,
+    nrf_uri: String,
+    nf_type: Option<String>,
+    validity_time: Option<DateTime>,
+    created_at: DateTime,
+}
+
+#[derive(Clone)]
+pub struct MongoStateStore {
+    db: Database,
+    available: Arc<AtomicBool>,
+    indexes_ready: Arc<AtomicBool>,
+    last_error: Arc<Mutex<Option<String>>>,
+    sticky_sessions: Collection<StickySessionDocument>,
+    bindings: Collection<BindingDocument>,
+    circuit_states: Collection<CircuitStateDocument>,
+    nf_profiles: Collection<NfProfileDocument>,
+    subscriptions: Collection<SubscriptionDocument>,
+    rate_limits: Collection<Document>,
+}
+
+impl MongoStateStore {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            db: db.clone(),
+            available: Arc::new(AtomicBool::new(false)),
+            indexes_ready: Arc::new(AtomicBool::new(false)),
+            last_error: Arc::new(Mutex::new(None)),
+            sticky_sessions: db.collection(STICKY_SESSIONS_COLLECTION),
+            bindings: db.collection(BINDINGS_COLLECTION),
+            circuit_states: db.collection(CIRCUIT_STATES_COLLECTION),
+            nf_profiles: db.collection(NF_PROFILES_COLLECTION),
+            subscriptions: db.collection(SUBSCRIPTIONS_COLLECTION),
+            rate_limits: db.collection(RATE_LIMITS_COLLECTION),
+        }
+    }
+
+    pub async fn init_indexes(&self) -> Result<()> {
+        self.sticky_sessions.create_index(expiry_index("expires_at")).await?;
+        self.bindings.create_index(expiry_index("expires_at")).await?;
+        self.subscriptions.create_index(expiry_index("validity_time")).await?;
+        self.rate_limits.create_index(expiry_index("expires_at")).await?;
+        Ok(())
+    }
+
+    pub async fn check_connectivity(&self) -> bool {
+        match self.db.run_command(doc! { "ping": 1 }).await {
+            Ok(_) => {
+                if !self.available.swap(true, Ordering::Relaxed) {
+                    tracing::info!("MongoDB state store is reachable");
+                }
+                *self.last_error.lock().unwrap() = None;
+
+                if !self.indexes_ready.load(Ordering::Relaxed) {
+                    match self.init_indexes().await {
+                        Ok(()) => self.indexes_ready.store(true, Ordering::Relaxed),
+                        Err(e) => tracing::warn!("Failed to create MongoDB state store indexes: {}", e),
+                    }
+                }
+                true
+            }
+            Err(e) => {
+                if self.available.swap(false, Ordering::Relaxed) {
+                    tracing::error!("MongoDB state store became unreachable: {}", e);
+                } else {
+                    tracing::debug!("MongoDB state store still unreachable: {}", e);
+                }
+                *self.last_error.lock().unwrap() = Some(e.to_string());
+                false
+            }
+        }
+    }
+
+    pub async fn run_monitor(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            self.check_connectivity().await;
+        }
+    }
+
+    fn ensure_available(&self) -> Result<()> {
+        if self.available.load(Ordering::Relaxed) {
+            Ok(())
+        } else {
+            anyhow::bail!("MongoDB state store is unavailable")
+        }
+    }
+}
+
+#[async_trait]
+impl StateStore for MongoStateStore {
+    fn backend(&self) -> &'static str {
+        "mongodb"
+    }
+
+    fn is_shared(&self) -> bool {
+        true
+    }
+
+    async fn ping(&self) -> Result<()> {
+        if !self.available.load(Ordering::Relaxed) {
+            let error = self.last_error.lock().unwrap().clone();
+            anyhow::bail!(error.unwrap_or_else(|| "MongoDB is unreachable".to_string()))
+        }
+
+        if self.check_connectivity().await {
+            Ok(())
+        } else {
+            let error = self.last_error.lock().unwrap().clone();
+            anyhow::bail!(error.unwrap_or_else(|| "MongoDB is unreachable".to_string()))
+        }
+    }
+
+    async fn get_sticky_session(&self, session_id: &str) -> Result<Option<StoredStickySession>> {
+        self.ensure_available()?;
+
+        let filter = doc! { "_id": session_id, "expires_at": { "$gt": DateTime::now() } };
+
+        Ok(self
+            .sticky_sessions
+            .find_one(filter)
+            .await?
+            .map(|document| StoredStickySession {
+                session_id: document.session_id,
+                nf_instance_id: document.nf_instance_id,
+                nf_type: document.nf_type,
+                expires_at: from_bson(document.expires_at),
+            }))
+    }
+
+    async fn put_sticky_session(&self, session: StoredStickySession) -> Result<()> {
+        self.ensure_available()?;
+
+        let document = StickySessionDocument {
+            session_id: session.session_id,
+            nf_instance_id: session.nf_instance_id,
+            nf_type: session.nf_type,
+            updated_at: DateTime::now(),
+            expires_at: to_bson(session.expires_at),
+        };
+
+        self.sticky_sessions
+            .replace_one(doc! { "_id": &document.session_id }, &document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_sticky_session(&self, session_id: &str) -> Result<bool> {
+        self.ensure_available()?;
+
+        let result = self.sticky_sessions.delete_one(doc! { "_id": session_id }).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    async fn find_bindings(&self, resource_paths: &[String]) -> Result<Vec<StoredBinding>> {
+        self.ensure_available()?;
+
+        let filter = doc! {
+            "_id": { "$in": resource_paths },
+            "expires_at": { "$gt": DateTime::now() },
+        };
+
+        Ok(find_all(&self.bindings, filter)
+            .await?
+            .into_iter()
+            .map(|document| StoredBinding {
+                resource_path: document.resource_path,
+                nf_instance_id: document.nf_instance_id,
+                nf_type: document.nf_type,
+                nf_set_id: document.nf_set_id,
+                binding_level: document.binding_level,
+                expires_at: from_bson(document.expires_at),
+            })
+            .collect())
+    }
+
+    async fn put_binding(&self, binding: StoredBinding) -> Result<()> {
+        self.ensure_available()?;
+
+        let document = BindingDocument {
+            resource_path: binding.resource_path,
+            nf_instance_id: binding.nf_instance_id,
+            nf_type: binding.nf_type,
+            nf_set_id: binding.nf_set_id,
+            binding_level: binding.binding_level,
+            updated_at: DateTime::now(),
+            expires_at: to_bson(binding.expires_at),
+        };
+
+        self.bindings
+            .replace_one(doc! { "_id": &document.resource_path }, &document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_circuit_state(&self, nf_instance_id: &str) -> Result<Option<StoredCircuitState>> {
+        self.ensure_available()?;
+
+        Ok(self
+            .circuit_states
+            .find_one(doc! { "_id": nf_instance_id })
+            .await?
+            .map(circuit_state_from_document))
+    }
+
+    async fn put_circuit_state(&self, state: StoredCircuitState) -> Result<()> {
+        self.ensure_available()?;
+
+        let document = CircuitStateDocument {
+            nf_instance_id: state.nf_instance_id,
+            nf_type: state.nf_type,
+            state: state.state,
+            open_until: state.open_until.map(to_bson),
+            updated_at: to_bson(state.updated_at),
+        };
+
+        self.circuit_states
+            .replace_one(doc! { "_id": &document.nf_instance_id }, &document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_circuit_states(&self) -> Result<Vec<StoredCircuitState>> {
+        self.ensure_available()?;
+
+        Ok(find_all(&self.circuit_states, doc! {})
+            .await?
+            .into_iter()
+            .map(circuit_state_from_document)
+            .collect())
+    }
+
+    async fn get_nf_profile(&self, nf_instance_id: &str) -> Result<Option<CachedNfProfile>> {
+        self.ensure_available()?;
+
+        Ok(self
+            .nf_profiles
+            .find_one(doc! { "_id": nf_instance_id })
+            .await?
+            .map(nf_profile_from_document))
+    }
+
+    async fn put_nf_profile(&self, profile: CachedNfProfile) -> Result<()> {
+        self.ensure_available()?;
+
+        let document = NfProfileDocument {
+            nf_instance_id: profile.profile.nf_instance_id.clone(),
+            profile: profile.profile,
+            cached_at: to_bson(profile.cached_at),
+        };
+
+        self.nf_profiles
+            .replace_one(doc! { "_id": &document.nf_instance_id }, &document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_nf_profile(&self, nf_instance_id: &str) -> Result<bool> {
+        self.ensure_available()?;
+
+        let result = self.nf_profiles.delete_one(doc! { "_id": nf_instance_id }).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    async fn list_nf_profiles(&self) -> Result<Vec<CachedNfProfile>> {
+        self.ensure_available()?;
+
+        Ok(find_all(&self.nf_profiles, doc! {})
+            .await?
+            .into_iter()
+            .map(nf_profile_from_document)
+            .collect())
+    }
+
+    async fn get_subscription(&self, subscription_id: &str) -> Result<Option<SubscriptionRecord>> {
+        self.ensure_available()?;
+
+        Ok(self
+            .subscriptions
+            .find_one(doc! { "_id": subscription_id })
+            .await?
+            .map(subscription_from_document))
+    }
+
+    async fn put_subscription(&self, subscription: SubscriptionRecord) -> Result<()> {
+        self.ensure_available()?;
+
+        let document = SubscriptionDocument {
+            subscription_id: subscription.subscription_id,
+            nrf_uri: subscription.nrf_uri,
+            nf_type: subscription.nf_type,
+            validity_time: subscription.validity_time.map(to_bson),
+            created_at: to_bson(subscription.created_at),
+        };
+
+        self.subscriptions
+            .replace_one(doc! { "_id": &document.subscription_id }, &document)
+            .upsert(true)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_subscription(&self, subscription_id: &str) -> Result<bool> {
+        self.ensure_available()?;
+
+        let result = self.subscriptions.delete_one(doc! { "_id": subscription_id }).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    async fn list_subscriptions(&self) -> Result<Vec<SubscriptionRecord>> {
+        self.ensure_available()?;
+
+        Ok(find_all(&self.subscriptions, doc! {})
+            .await?
+            .into_iter()
+            .map(subscription_from_document)
+            .collect())
+    }
+
+    async fn take_rate_limit_token(&self, key: &str, rate_per_second: f64, burst: f64) -> Result<bool> {
+        self.ensure_available()?;
+
+        let refill_ms = (burst / rate_per_second * 1000.0) as i64;
+        let pipeline = vec![
+            doc! {
+                "$set": {
+                    "tokens": {
+                        "$min": [
+                            burst,
+                            {
+                                "$add": [
+                                    { "$ifNull": ["$tokens", burst] },
+                                    {
+                                        "$multiply": [
+                                            rate_per_second,
+                                            {
+                                                "$divide": [
+                                                    { "$subtract": ["$$NOW", { "$ifNull": ["$updated_at", "$$NOW"] }] },
+                                                    1000,
+                                                ]
+                                            },
+                                        ]
+                                    },
+                                ]
+                            },
+                        ]
+                    },
+                    "updated_at": "$$NOW",
+                }
+            },
+            doc! { "$set": { "allowed": { "$gte": ["$tokens", 1] } } },
+            doc! {
+                "$set": {
+                    "tokens": { "$cond": ["$allowed", { "$subtract": ["$tokens", 1] }, "$tokens"] },
+                    "expires_at": { "$add": ["$$NOW", refill_ms + RATE_LIMIT_IDLE_MS] },
+                }
+            },
+        ];
+
+        let document = self
+            .rate_limits
+            .find_one_and_update(doc! { "_id": key }, pipeline)
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await?;
+
+        Ok(document
+            .and_then(|document| document.get_bool("allowed").ok())
+            .unwrap_or(true))
+    }
+
+    async fn purge_expired(&self) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+fn expiry_index(field: &str) -> IndexModel {
+    IndexModel::builder()
+        .keys(doc! { field: 1 })
+        .options(
+            IndexOptions::builder()
+                .expire_after(std::time::Duration::from_secs(0))
+                .name(format!("{}_ttl", field))
+                .build(),
+        )
+        .build()
+}
+
+async fn find_all<T>(collection: &Collection<T>, filter: Document) -> Result<Vec<T>>
+where
+    T: DeserializeOwned + Send + Sync,
+{
+    let mut cursor = collection.find(filter).await?;
+    let mut documents = Vec::new();
+
+    while cursor.advance().await? {
+        documents.push(cursor.deserialize_current()?);
+    }
+
+    Ok(documents)
+}
+
+fn to_bson(value: chrono::DateTime<Utc>) -> DateTime {
+    DateTime::from_millis(value.timestamp_millis())
+}
+
+fn from_bson(value: DateTime) -> chrono::DateTime<Utc> {
+    chrono::DateTime::from_timestamp_millis(value.timestamp_millis()).unwrap_or_default()
+}
+
+fn circuit_state_from_document(document: CircuitStateDocument) -> StoredCircuitState {
+    StoredCircuitState {
+        nf_instance_id: document.nf_instance_id,
+        nf_type: document.nf_type,
+        state: document.state,
+        open_until: document.open_until.map(from_bson),
+        updated_at: from_bson(document.updated_at),
+    }
+}
+
+fn nf_profile_from_document(document: NfProfileDocument) -> CachedNfProfile {
+    CachedNfProfile {
+        profile: document.profile,
+        cached_at: from_bson(document.cached_at),
+    }
+}
+
+fn subscription_from_document(document: SubscriptionDocument) -> SubscriptionRecord {
+    SubscriptionRecord {
+        subscription_id: document.subscription_id,
+        nrf_uri: document.nrf_uri,
+        nf_type: document.nf_type,
+        validity_time: document.validity_time.map(from_bson),
+        created_at: from_bson(document.created_at),
+    }
+}
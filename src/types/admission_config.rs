@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub const DEFAULT_MESSAGE_PRIORITY: u8 = 24;
+pub const LOWEST_MESSAGE_PRIORITY: u8 = 31;
+
+#[derive(Debug, Clone)]
+pub struct AdmissionConfig {
+    pub max_in_flight: usize,
+    pub default_nf_type_limit: usize,
+    pub nf_type_limits: HashMap<String, usize>,
+    pub shed_threshold_percent: u32,
+    pub retry_after_seconds: u64,
+    pub overload_validity_seconds: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 0,
+            default_nf_type_limit: 0,
+            nf_type_limits: HashMap::new(),
+            shed_threshold_percent: 80,
+            retry_after_seconds: 1,
+            overload_validity_seconds: 30,
+        }
+    }
+}
+
+impl AdmissionConfig {
+    pub fn nf_type_limit(&self, nf_type: &str) -> usize {
+        self.nf_type_limits
+            .get(nf_type)
+            .copied()
+            .unwrap_or(self.default_nf_type_limit)
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        Duration::from_secs(self.retry_after_seconds.max(1))
+    }
+}
@@ -1,18 +1,42 @@
 use std::sync::Arc;
 use uuid::Uuid;
-use dashmap::DashMap;
-use std::time::Instant;
-use super::nf_profile::CachedNfProfile;
+use std::time::{Duration, Instant};
+use super::readiness_config::ReadinessConfig;
 use super::retry_config::RetryConfig;
+use crate::services::admission::AdmissionController;
+use crate::services::callback_router::CallbackRouter;
+use crate::services::discovery_cache::DiscoveryCache;
+use crate::services::lifecycle::Lifecycle;
 use crate::services::load_balancer::LoadBalancer;
+use crate::services::metrics::Metrics;
+use crate::services::nrf_registration::NrfRegistration;
+use crate::services::outlier_detection::OutlierDetector;
+use crate::services::rate_limiter::RateLimiter;
+use crate::services::retry_budget::RetryBudget;
+use crate::services::scp_router::ScpRouter;
+use crate::services::session_store::SessionStore;
+use crate::store::StateStore;
 
 #[derive(Clone)]
 pub struct AppState {
     pub nf_instance_id: Uuid,
     pub nrf_client: Option<Arc<crate::clients::nrf::NrfClient>>,
+    pub nrf_registration: Option<NrfRegistration>,
     pub http_client: reqwest::Client,
-    pub nf_profile_cache: Arc<DashMap<String, CachedNfProfile>>,
+    pub nf_profile_cache: DiscoveryCache,
     pub load_balancer: LoadBalancer,
+    pub session_store: SessionStore,
+    pub state_store: Arc<dyn StateStore>,
+    pub outlier_detector: OutlierDetector,
     pub retry_config: RetryConfig,
+    pub retry_budget: RetryBudget,
+    pub metrics: Metrics,
+    pub default_max_rsp_time: Duration,
+    pub callback_router: CallbackRouter,
+    pub scp_router: ScpRouter,
+    pub lifecycle: Lifecycle,
+    pub admission: AdmissionController,
+    pub rate_limiter: RateLimiter,
+    pub readiness: ReadinessConfig,
     pub start_time: Instant,
 }
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerConfig {
+    pub failure_rate_threshold: f64,
+    pub minimum_requests: usize,
+    pub window_seconds: u64,
+    pub open_seconds: u64,
+    pub half_open_max_requests: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 5,
+            window_seconds: 30,
+            open_seconds: 30,
+            half_open_max_requests: 3,
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_seconds)
+    }
+
+    pub fn open_duration(&self) -> Duration {
+        Duration::from_secs(self.open_seconds)
+    }
+
+    pub fn apply(&mut self, param: &str, value: &str) -> bool {
+        match param {
+            "FAILURE_RATE_THRESHOLD" => value.parse().map(|v| self.failure_rate_threshold = v).is_ok(),
+            "MINIMUM_REQUESTS" => value.parse().map(|v| self.minimum_requests = v).is_ok(),
+            "WINDOW_SECONDS" => value.parse().map(|v| self.window_seconds = v).is_ok(),
+            "OPEN_SECONDS" => value.parse().map(|v| self.open_seconds = v).is_ok(),
+            "HALF_OPEN_MAX_REQUESTS" => value.parse().map(|v| self.half_open_max_requests = v).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerSettings {
+    pub default: CircuitBreakerConfig,
+    pub nf_types: HashMap<String, CircuitBreakerConfig>,
+}
+
+impl CircuitBreakerSettings {
+    pub fn for_nf_type(&self, nf_type: Option<&str>) -> &CircuitBreakerConfig {
+        nf_type
+            .and_then(|nf_type| self.nf_types.get(nf_type))
+            .unwrap_or(&self.default)
+    }
+}
@@ -1,12 +1,15 @@
-use axum::http::StatusCode;
+use axum::extract::rejection::JsonRejection;
+use axum::http::header::LOCATION;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
-use super::problem_details::ProblemDetails;
+use super::problem_details::{ProblemDetails, SbiCause};
 
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     #[error("Internal server error: {0}")]
     InternalError(String),
 
+    #[allow(dead_code)]
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
@@ -30,60 +33,147 @@ pub enum AppError {
 
     #[error("Bad gateway: {0}")]
     BadGateway(String),
+
+    #[error("Loop detected: {0}")]
+    LoopDetected(String),
+
+    #[error("NF discovery failed: {0}")]
+    NfDiscoveryFailure(String),
+
+    #[error("Target NF not reachable: {0}")]
+    TargetNfNotReachable(String),
+
+    #[error("NF failover failed: {0}")]
+    NfFailover(String),
+
+    #[error("Insufficient resources: {0}")]
+    InsufficientResources(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Redirected to {location}")]
+    ScpRedirection { status: StatusCode, location: String },
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, title, detail) = match &self {
+        let (status, title, detail, cause) = match &self {
             AppError::InternalError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal Server Error",
                 msg.as_str(),
+                Some(SbiCause::SystemFailure),
             ),
             AppError::ConfigError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Configuration Error",
                 msg.as_str(),
+                Some(SbiCause::SystemFailure),
             ),
             AppError::NotFound(msg) => (
                 StatusCode::NOT_FOUND,
                 "Not Found",
                 msg.as_str(),
+                None,
             ),
             AppError::BadRequest(msg) => (
                 StatusCode::BAD_REQUEST,
                 "Bad Request",
                 msg.as_str(),
+                None,
             ),
             AppError::Unauthorized(msg) => (
                 StatusCode::UNAUTHORIZED,
                 "Unauthorized",
                 msg.as_str(),
+                None,
             ),
             AppError::Forbidden(msg) => (
                 StatusCode::FORBIDDEN,
                 "Forbidden",
                 msg.as_str(),
+                None,
             ),
             AppError::ServiceUnavailable(msg) => (
                 StatusCode::SERVICE_UNAVAILABLE,
                 "Service Unavailable",
                 msg.as_str(),
+                None,
             ),
             AppError::GatewayTimeout(msg) => (
                 StatusCode::GATEWAY_TIMEOUT,
                 "Gateway Timeout",
                 msg.as_str(),
+                Some(SbiCause::TimedOutRequest),
             ),
             AppError::BadGateway(msg) => (
                 StatusCode::BAD_GATEWAY,
                 "Bad Gateway",
                 msg.as_str(),
+                None,
+            ),
+            AppError::LoopDetected(msg) => (
+                StatusCode::LOOP_DETECTED,
+                "Loop Detected",
+                msg.as_str(),
+                Some(SbiCause::LoopDetected),
+            ),
+            AppError::NfDiscoveryFailure(msg) => (
+                StatusCode::GATEWAY_TIMEOUT,
+                "Gateway Timeout",
+                msg.as_str(),
+                Some(SbiCause::NfDiscoveryFailure),
+            ),
+            AppError::TargetNfNotReachable(msg) => (
+                StatusCode::GATEWAY_TIMEOUT,
+                "Gateway Timeout",
+                msg.as_str(),
+                Some(SbiCause::TargetNfNotReachable),
+            ),
+            AppError::NfFailover(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service Unavailable",
+                msg.as_str(),
+                Some(SbiCause::NfFailover),
+            ),
+            AppError::InsufficientResources(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service Unavailable",
+                msg.as_str(),
+                Some(SbiCause::InsufficientResources),
+            ),
+            AppError::TooManyRequests(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too Many Requests",
+                msg.as_str(),
+                Some(SbiCause::NfCongestionRisk),
+            ),
+            AppError::ScpRedirection { status, location } => (
+                *status,
+                status.canonical_reason().unwrap_or("Redirect"),
+                location.as_str(),
+                Some(SbiCause::ScpRedirection),
             ),
         };
 
-        let problem = ProblemDetails::new(status.as_u16(), title, detail);
-        problem.into_response()
+        let mut problem = ProblemDetails::new(status.as_u16(), title, detail);
+        if let Some(cause) = cause {
+            problem = problem.with_cause(cause.as_str());
+        }
+        let mut response = problem.into_response();
+        if let AppError::ScpRedirection { location, .. } = &self {
+            if let Ok(location) = HeaderValue::from_str(location) {
+                response.headers_mut().insert(LOCATION, location);
+            }
+        }
+        response
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        AppError::BadRequest(rejection.body_text())
     }
 }
 
@@ -93,6 +183,7 @@ impl From<anyhow::Error> for AppError {
     }
 }
 
+#[cfg(feature = "mongodb")]
 impl From<mongodb::error::Error> for AppError {
     fn from(err: mongodb::error::Error) -> Self {
         AppError::InternalError(format!("Database error: {}", err))
@@ -104,11 +195,70 @@ impl From<reqwest::Error> for AppError {
         if err.is_timeout() {
             AppError::GatewayTimeout(err.to_string())
         } else if err.is_connect() {
-            AppError::ServiceUnavailable(format!("Failed to connect to upstream service: {}", err))
+            AppError::TargetNfNotReachable(format!("Failed to connect to upstream service: {}", err))
         } else {
             AppError::BadGateway(err.to_string())
         }
     }
 }
 
+#[allow(dead_code)]
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PROBLEM_JSON;
+    use axum::http::header::CONTENT_TYPE;
+
+    async fn problem_details(response: Response) -> ProblemDetails {
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn errors_map_to_status_and_cause() {
+        let detail = || "detail".to_string();
+        let cases = [
+            (AppError::InternalError(detail()), StatusCode::INTERNAL_SERVER_ERROR, Some("SYSTEM_FAILURE")),
+            (AppError::ConfigError(detail()), StatusCode::INTERNAL_SERVER_ERROR, Some("SYSTEM_FAILURE")),
+            (AppError::NotFound(detail()), StatusCode::NOT_FOUND, None),
+            (AppError::BadRequest(detail()), StatusCode::BAD_REQUEST, None),
+            (AppError::Unauthorized(detail()), StatusCode::UNAUTHORIZED, None),
+            (AppError::Forbidden(detail()), StatusCode::FORBIDDEN, None),
+            (AppError::ServiceUnavailable(detail()), StatusCode::SERVICE_UNAVAILABLE, None),
+            (AppError::GatewayTimeout(detail()), StatusCode::GATEWAY_TIMEOUT, Some("TIMED_OUT_REQUEST")),
+            (AppError::BadGateway(detail()), StatusCode::BAD_GATEWAY, None),
+            (AppError::LoopDetected(detail()), StatusCode::LOOP_DETECTED, Some("LOOP_DETECTED")),
+            (AppError::NfDiscoveryFailure(detail()), StatusCode::GATEWAY_TIMEOUT, Some("NF_DISCOVERY_FAILURE")),
+            (AppError::TargetNfNotReachable(detail()), StatusCode::GATEWAY_TIMEOUT, Some("TARGET_NF_NOT_REACHABLE")),
+            (AppError::NfFailover(detail()), StatusCode::SERVICE_UNAVAILABLE, Some("NF_FAILOVER")),
+            (AppError::InsufficientResources(detail()), StatusCode::SERVICE_UNAVAILABLE, Some("INSUFFICIENT_RESOURCES")),
+            (AppError::TooManyRequests(detail()), StatusCode::TOO_MANY_REQUESTS, Some("NF_CONGESTION_RISK")),
+        ];
+
+        for (error, status, cause) in cases {
+            let response = error.into_response();
+            assert_eq!(response.status(), status);
+
+            let problem = problem_details(response).await;
+            assert_eq!(problem.status, Some(status.as_u16()));
+            assert_eq!(problem.cause.as_deref(), cause);
+            assert_eq!(problem.detail.as_deref(), Some("detail"));
+        }
+    }
+
+    #[tokio::test]
+    async fn scp_redirection_carries_the_location() {
+        let response = AppError::ScpRedirection {
+            status: StatusCode::PERMANENT_REDIRECT,
+            location: "http://udm-2.example/nudm-sdm/v2/imsi-1".to_string(),
+        }
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers().get(LOCATION).unwrap(), "http://udm-2.example/nudm-sdm/v2/imsi-1");
+        assert_eq!(problem_details(response).await.cause.as_deref(), Some("SCP_REDIRECTION"));
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthCheckKind {
+    Tcp,
+    Http2Ping,
+    Http,
+}
+
+impl HealthCheckKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "tcp" => Some(HealthCheckKind::Tcp),
+            "http2_ping" | "h2_ping" => Some(HealthCheckKind::Http2Ping),
+            "http" | "http_get" => Some(HealthCheckKind::Http),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthCheckKind::Tcp => "tcp",
+            HealthCheckKind::Http2Ping => "http2_ping",
+            HealthCheckKind::Http => "http",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthCheckConfig {
+    pub kind: HealthCheckKind,
+    pub http_path: String,
+    pub timeout_ms: u64,
+    pub healthy_threshold: usize,
+    pub unhealthy_threshold: usize,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            kind: HealthCheckKind::Tcp,
+            http_path: "/".to_string(),
+            timeout_ms: 2000,
+            healthy_threshold: 2,
+            unhealthy_threshold: 3,
+        }
+    }
+}
+
+impl HealthCheckConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    pub fn apply(&mut self, param: &str, value: &str) -> bool {
+        match param {
+            "TYPE" => HealthCheckKind::parse(value).map(|v| self.kind = v).is_some(),
+            "HTTP_PATH" => {
+                self.http_path = value.to_string();
+                true
+            }
+            "TIMEOUT_MS" => value.parse().map(|v| self.timeout_ms = v).is_ok(),
+            "HEALTHY_THRESHOLD" => value.parse().map(|v| self.healthy_threshold = v).is_ok(),
+            "UNHEALTHY_THRESHOLD" => value.parse().map(|v| self.unhealthy_threshold = v).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthCheckSettings {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    pub default: HealthCheckConfig,
+    pub nf_types: HashMap<String, HealthCheckConfig>,
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 10,
+            default: HealthCheckConfig::default(),
+            nf_types: HashMap::new(),
+        }
+    }
+}
+
+impl HealthCheckSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds.max(1))
+    }
+
+    pub fn for_nf_type(&self, nf_type: &str) -> &HealthCheckConfig {
+        self.nf_types.get(nf_type).unwrap_or(&self.default)
+    }
+}
@@ -1,13 +1,36 @@
+pub mod admission_config;
 pub mod app_state;
+pub mod circuit_breaker_config;
 pub mod error;
+pub mod health_check_config;
 pub mod problem_details;
 pub mod nf_profile;
 pub mod nrf_notification;
+pub mod outlier_detection_config;
+pub mod rate_limit_config;
+pub mod readiness_config;
 pub mod retry_config;
+pub mod scp_profile_config;
+pub mod scp_routing_config;
+pub mod shutdown_config;
+pub mod state_store_config;
+pub mod sticky_session_config;
 
+pub use admission_config::*;
 pub use app_state::*;
+pub use circuit_breaker_config::*;
 pub use error::*;
+pub use health_check_config::*;
+#[allow(unused_imports)]
 pub use problem_details::*;
 pub use nf_profile::*;
 pub use nrf_notification::*;
+pub use outlier_detection_config::*;
+pub use rate_limit_config::*;
+pub use readiness_config::*;
 pub use retry_config::*;
+pub use scp_profile_config::*;
+pub use scp_routing_config::*;
+pub use shutdown_config::*;
+pub use state_store_config::*;
+pub use sticky_session_config::*;
@@ -1,6 +1,82 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlmnId {
+    pub mcc: String,
+    pub mnc: String,
+}
+
+impl PlmnId {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (mcc, mnc) = value.trim().split_once('-')?;
+        let valid = |digits: &str, lengths: &[usize]| {
+            lengths.contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+        };
+
+        if !valid(mcc, &[3]) || !valid(mnc, &[2, 3]) {
+            return None;
+        }
+
+        Some(Self {
+            mcc: mcc.to_string(),
+            mnc: mnc.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ipv4AddressRange {
+    pub start: String,
+    pub end: String,
+}
+
+impl Ipv4AddressRange {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (start, end) = value.trim().split_once('-')?;
+        let start: std::net::Ipv4Addr = start.trim().parse().ok()?;
+        let end: std::net::Ipv4Addr = end.trim().parse().ok()?;
+
+        if start > end {
+            return None;
+        }
+
+        Some(Self {
+            start: start.to_string(),
+            end: end.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScpInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scp_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scp_ports: BTreeMap<String, u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address_domains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ipv4_addresses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ipv4_addr_ranges: Vec<Ipv4AddressRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub served_nf_set_id_list: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_plmn_list: Vec<PlmnId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scp_capabilities: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NfService {
+    pub service_instance_id: String,
+    pub service_name: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NfProfile {
     pub nf_instance_id: String,
@@ -13,10 +89,42 @@ pub struct NfProfile {
     pub capacity: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heart_beat_timer: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plmn_list: Vec<PlmnId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locality: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nf_set_id_list: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scp_domains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scp_info: Option<ScpInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nf_services: Vec<NfService>,
+}
+
+impl NfProfile {
+    pub fn service_instance_id(&self, service_name: &str) -> Option<&str> {
+        self.nf_services
+            .iter()
+            .find(|service| service.service_name == service_name)
+            .map(|service| service.service_instance_id.as_str())
+    }
+
+    pub fn api_root(&self) -> Option<String> {
+        if let Some(fqdn) = &self.fqdn {
+            Some(format!("http://{}", fqdn))
+        } else {
+            self.ipv4_addresses.first().map(|ip| format!("http://{}", ip))
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct CachedNfProfile {
     pub profile: NfProfile,
+    #[cfg_attr(not(feature = "mongodb"), allow(dead_code))]
     pub cached_at: chrono::DateTime<chrono::Utc>,
 }
@@ -3,6 +3,7 @@ use super::NfProfile;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(clippy::enum_variant_names)]
 pub enum NotificationEventType {
     NfRegistered,
     NfDeregistered,
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct OutlierDetectionConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    pub base_ejection_seconds: u64,
+    pub max_ejection_seconds: u64,
+    pub max_ejection_percent: u32,
+    pub success_rate_minimum_hosts: usize,
+    pub success_rate_request_volume: u64,
+    pub success_rate_stdev_factor: f64,
+    pub latency_minimum_hosts: usize,
+    pub latency_request_volume: usize,
+    pub latency_p99_factor: f64,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 10,
+            base_ejection_seconds: 30,
+            max_ejection_seconds: 300,
+            max_ejection_percent: 50,
+            success_rate_minimum_hosts: 3,
+            success_rate_request_volume: 20,
+            success_rate_stdev_factor: 1.9,
+            latency_minimum_hosts: 3,
+            latency_request_volume: 20,
+            latency_p99_factor: 3.0,
+        }
+    }
+}
+
+impl OutlierDetectionConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds.max(1))
+    }
+
+    pub fn ejection_duration(&self, ejection_count: u32) -> Duration {
+        let seconds = self
+            .base_ejection_seconds
+            .saturating_mul(ejection_count.max(1) as u64)
+            .min(self.max_ejection_seconds);
+        Duration::from_secs(seconds)
+    }
+}
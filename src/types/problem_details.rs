@@ -1,6 +1,41 @@
-use axum::{http::StatusCode, response::{IntoResponse, Response}, Json};
+use axum::{
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::{Deserialize, Serialize};
 
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbiCause {
+    SystemFailure,
+    NfDiscoveryFailure,
+    TargetNfNotReachable,
+    ScpRedirection,
+    TimedOutRequest,
+    NfFailover,
+    InsufficientResources,
+    LoopDetected,
+    NfCongestionRisk,
+}
+
+impl SbiCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SbiCause::SystemFailure => "SYSTEM_FAILURE",
+            SbiCause::NfDiscoveryFailure => "NF_DISCOVERY_FAILURE",
+            SbiCause::TargetNfNotReachable => "TARGET_NF_NOT_REACHABLE",
+            SbiCause::ScpRedirection => "SCP_REDIRECTION",
+            SbiCause::TimedOutRequest => "TIMED_OUT_REQUEST",
+            SbiCause::NfFailover => "NF_FAILOVER",
+            SbiCause::InsufficientResources => "INSUFFICIENT_RESOURCES",
+            SbiCause::LoopDetected => "LOOP_DETECTED",
+            SbiCause::NfCongestionRisk => "NF_CONGESTION_RISK",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProblemDetails {
@@ -41,11 +76,13 @@ impl ProblemDetails {
         }
     }
 
+    #[allow(dead_code)]
     pub fn with_type(mut self, r#type: &str) -> Self {
         self.r#type = Some(r#type.to_string());
         self
     }
 
+    #[allow(dead_code)]
     pub fn with_instance(mut self, instance: &str) -> Self {
         self.instance = Some(instance.to_string());
         self
@@ -61,6 +98,10 @@ impl IntoResponse for ProblemDetails {
     fn into_response(self) -> Response {
         let status = StatusCode::from_u16(self.status.unwrap_or(500))
             .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        (status, Json(self)).into_response()
+        let mut response = (status, Json(self)).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response
     }
 }
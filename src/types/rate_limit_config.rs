@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const RATE_LIMIT_ANY: &str = "*";
+
+pub const MIN_RATE_PER_SECOND: f64 = 0.001;
+
+fn any() -> String {
+    RATE_LIMIT_ANY.to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitRule {
+    #[serde(default = "any")]
+    pub consumer: String,
+    #[serde(default = "any")]
+    pub target: String,
+    pub rate_per_second: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<f64>,
+}
+
+impl RateLimitRule {
+    pub fn parse(entry: &str) -> Option<Self> {
+        let mut rule = Self {
+            consumer: any(),
+            target: any(),
+            rate_per_second: 0.0,
+            burst: None,
+        };
+
+        for parameter in entry.split(';') {
+            let (key, value) = parameter.split_once('=')?;
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "consumer" => rule.consumer = value.to_string(),
+                "target" => rule.target = value.to_string(),
+                "rate" => rule.rate_per_second = value.parse().ok()?,
+                "burst" => rule.burst = Some(value.parse().ok()?),
+                _ => return None,
+            }
+        }
+
+        rule.is_valid().then_some(rule)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.rate_per_second.is_finite()
+            && self.rate_per_second >= MIN_RATE_PER_SECOND
+            && self.burst.is_none_or(|burst| burst.is_finite() && burst >= 1.0)
+            && !self.consumer.trim().is_empty()
+            && !self.target.trim().is_empty()
+    }
+
+    pub fn burst(&self) -> f64 {
+        self.burst.unwrap_or(self.rate_per_second.max(1.0))
+    }
+
+    pub fn specificity(&self, consumer: &str, nf_type: &str, service_name: &str) -> Option<u8> {
+        let consumer_score = if self.consumer == RATE_LIMIT_ANY {
+            0
+        } else if self.consumer == consumer {
+            4
+        } else {
+            return None;
+        };
+
+        let target_score = if self.target == RATE_LIMIT_ANY {
+            0
+        } else if self.target.eq_ignore_ascii_case(service_name) {
+            2
+        } else if self.target.eq_ignore_ascii_case(nf_type) {
+            1
+        } else {
+            return None;
+        };
+
+        Some(consumer_score + target_score)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub rules: Vec<RateLimitRule>,
+    pub rules_file: Option<String>,
+    pub reload_interval_seconds: u64,
+    pub shared: bool,
+    pub shared_timeout_ms: u64,
+    pub peer_identity_header: Option<String>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: Vec::new(),
+            rules_file: None,
+            reload_interval_seconds: 10,
+            shared: false,
+            shared_timeout_ms: 50,
+            peer_identity_header: None,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn reload_interval(&self) -> Duration {
+        Duration::from_secs(self.reload_interval_seconds.max(1))
+    }
+
+    pub fn shared_timeout(&self) -> Duration {
+        Duration::from_millis(self.shared_timeout_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(consumer: &str, target: &str) -> RateLimitRule {
+        RateLimitRule {
+            consumer: consumer.to_string(),
+            target: target.to_string(),
+            rate_per_second: 10.0,
+            burst: None,
+        }
+    }
+
+    #[test]
+    fn parse_reads_rule_parameters() {
+        let rule = RateLimitRule::parse("consumer=amf-1; target=nudm-sdm; rate=5; burst=20").unwrap();
+
+        assert_eq!(rule.consumer, "amf-1");
+        assert_eq!(rule.target, "nudm-sdm");
+        assert_eq!(rule.rate_per_second, 5.0);
+        assert_eq!(rule.burst(), 20.0);
+    }
+
+    #[test]
+    fn parse_defaults_to_any_consumer_and_target() {
+        let rule = RateLimitRule::parse("rate=0.5").unwrap();
+
+        assert_eq!(rule.consumer, RATE_LIMIT_ANY);
+        assert_eq!(rule.target, RATE_LIMIT_ANY);
+        assert_eq!(rule.burst(), 1.0);
+    }
+
+    #[test]
+    fn parse_accepts_the_minimum_rate() {
+        let rule = RateLimitRule::parse("rate=0.001").unwrap();
+
+        assert_eq!(rule.rate_per_second, MIN_RATE_PER_SECOND);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_rules() {
+        assert!(RateLimitRule::parse("consumer=amf-1").is_none());
+        assert!(RateLimitRule::parse("rate=0").is_none());
+        assert!(RateLimitRule::parse("rate=-1").is_none());
+        assert!(RateLimitRule::parse("rate=NaN").is_none());
+        assert!(RateLimitRule::parse("rate=1e-20").is_none());
+        assert!(RateLimitRule::parse("rate=0.0009").is_none());
+        assert!(RateLimitRule::parse("rate=5;burst=0.5").is_none());
+        assert!(RateLimitRule::parse("rate=5;target=").is_none());
+        assert!(RateLimitRule::parse("rate=5;unknown=1").is_none());
+        assert!(RateLimitRule::parse("rate").is_none());
+    }
+
+    #[test]
+    fn specificity_prefers_consumer_then_service_then_nf_type() {
+        assert_eq!(rule("*", "*").specificity("amf-1", "UDM", "nudm-sdm"), Some(0));
+        assert_eq!(rule("*", "udm").specificity("amf-1", "UDM", "nudm-sdm"), Some(1));
+        assert_eq!(rule("*", "NUDM-SDM").specificity("amf-1", "UDM", "nudm-sdm"), Some(2));
+        assert_eq!(rule("amf-1", "*").specificity("amf-1", "UDM", "nudm-sdm"), Some(4));
+        assert_eq!(rule("amf-1", "nudm-sdm").specificity("amf-1", "UDM", "nudm-sdm"), Some(6));
+    }
+
+    #[test]
+    fn specificity_excludes_rules_for_other_consumers_or_targets() {
+        assert_eq!(rule("amf-2", "*").specificity("amf-1", "UDM", "nudm-sdm"), None);
+        assert_eq!(rule("*", "AUSF").specificity("amf-1", "UDM", "nudm-sdm"), None);
+        assert_eq!(rule("*", "nudm-uecm").specificity("amf-1", "UDM", "nudm-sdm"), None);
+    }
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct ReadinessConfig {
+    pub critical_nf_types: Vec<String>,
+    pub max_missed_heartbeats: u32,
+    pub tls_cert_path: Option<String>,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            critical_nf_types: Vec::new(),
+            max_missed_heartbeats: 3,
+            tls_cert_path: None,
+        }
+    }
+}
@@ -1,11 +1,114 @@
+use axum::http::{Method, StatusCode};
+use rand::Rng;
 use std::time::Duration;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JitterMode {
+    None,
+    Full,
+    Decorrelated,
+}
+
+impl JitterMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "none" => Some(JitterMode::None),
+            "full" => Some(JitterMode::Full),
+            "decorrelated" => Some(JitterMode::Decorrelated),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NonIdempotentRetryRule {
+    pub method: Option<Method>,
+    pub path_prefix: String,
+}
+
+impl NonIdempotentRetryRule {
+    pub fn parse(rule: &str) -> Option<Self> {
+        let mut parts = rule.split_whitespace();
+        let method = parts.next()?;
+        let path_prefix = parts.next().unwrap_or("*");
+
+        let method = if method == "*" {
+            None
+        } else {
+            Some(Method::from_bytes(method.to_uppercase().as_bytes()).ok()?)
+        };
+
+        Some(Self {
+            method,
+            path_prefix: path_prefix.to_string(),
+        })
+    }
+
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        let method_matches = self.method.as_ref().is_none_or(|m| m == method);
+        let path_matches = self.path_prefix == "*" || path.starts_with(&self.path_prefix);
+        method_matches && path_matches
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusAction {
+    Retry,
+    Reselect,
+    PassThrough,
+}
+
+impl StatusAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "retry" => Some(StatusAction::Retry),
+            "reselect" => Some(StatusAction::Reselect),
+            "pass" | "passthrough" | "pass_through" => Some(StatusAction::PassThrough),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusActionRule {
+    pub status: Option<u16>,
+    pub class: u16,
+    pub action: StatusAction,
+}
+
+impl StatusActionRule {
+    pub fn parse(rule: &str) -> Option<Self> {
+        let (status, action) = rule.split_once('=')?;
+        let status = status.trim().to_lowercase();
+        let action = StatusAction::parse(action)?;
+
+        if let Some(class) = status.strip_suffix("xx") {
+            let class: u16 = class.parse().ok()?;
+            return (1..=5).contains(&class).then_some(Self {
+                status: None,
+                class,
+                action,
+            });
+        }
+
+        let code = StatusCode::from_u16(status.parse().ok()?).ok()?.as_u16();
+        Some(Self {
+            status: Some(code),
+            class: code / 100,
+            action,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
     pub max_attempts: u32,
     pub initial_backoff_ms: u64,
     pub max_backoff_ms: u64,
     pub backoff_multiplier: f64,
+    pub jitter: JitterMode,
+    pub non_idempotent_rules: Vec<NonIdempotentRetryRule>,
+    pub status_actions: Vec<StatusActionRule>,
 }
 
 impl Default for RetryConfig {
@@ -15,11 +118,30 @@ impl Default for RetryConfig {
             initial_backoff_ms: 100,
             max_backoff_ms: 5000,
             backoff_multiplier: 2.0,
+            jitter: JitterMode::Full,
+            non_idempotent_rules: Vec::new(),
+            status_actions: Vec::new(),
         }
     }
 }
 
 impl RetryConfig {
+    pub fn status_action(&self, status: StatusCode) -> StatusAction {
+        let code = status.as_u16();
+        let exact = self.status_actions.iter().find(|rule| rule.status == Some(code));
+        let class = || {
+            self.status_actions
+                .iter()
+                .find(|rule| rule.status.is_none() && rule.class == code / 100)
+        };
+
+        match exact.or_else(class) {
+            Some(rule) => rule.action,
+            None if status.is_server_error() => StatusAction::Retry,
+            None => StatusAction::PassThrough,
+        }
+    }
+
     pub fn calculate_backoff(&self, attempt: u32) -> Duration {
         let backoff_ms = (self.initial_backoff_ms as f64
             * self.backoff_multiplier.powi(attempt as i32))
@@ -27,4 +149,108 @@ impl RetryConfig {
 
         Duration::from_millis(backoff_ms)
     }
+
+    pub fn jittered_backoff(&self, attempt: u32, previous: Option<Duration>) -> Duration {
+        let mut rng = rand::thread_rng();
+
+        match self.jitter {
+            JitterMode::None => self.calculate_backoff(attempt),
+            JitterMode::Full => {
+                let ceiling = self.calculate_backoff(attempt).as_millis() as u64;
+                Duration::from_millis(rng.gen_range(0..=ceiling))
+            }
+            JitterMode::Decorrelated => {
+                let base = self.initial_backoff_ms;
+                let upper = previous
+                    .map(|previous| previous.as_millis() as u64 * 3)
+                    .unwrap_or(base)
+                    .max(base);
+                let backoff_ms = rng.gen_range(base..=upper).min(self.max_backoff_ms);
+                Duration::from_millis(backoff_ms)
+            }
+        }
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        Duration::from_millis(self.max_backoff_ms)
+    }
+
+    pub fn is_idempotent(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE | Method::TRACE
+        )
+    }
+
+    pub fn allows_retry_after_send(&self, method: &Method, path: &str, producer_signalled_safe: bool) -> bool {
+        Self::is_idempotent(method)
+            || producer_signalled_safe
+            || self.non_idempotent_rules.iter().any(|rule| rule.matches(method, path))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryBudgetConfig {
+    pub enabled: bool,
+    pub ratio: f64,
+    pub max_tokens: f64,
+}
+
+impl Default for RetryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ratio: 0.2,
+            max_tokens: 10.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(jitter: JitterMode) -> RetryConfig {
+        RetryConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1000,
+            jitter,
+            ..RetryConfig::default()
+        }
+    }
+
+    #[test]
+    fn no_jitter_uses_exponential_backoff_capped_at_max() {
+        let config = config(JitterMode::None);
+
+        assert_eq!(config.jittered_backoff(0, None), Duration::from_millis(100));
+        assert_eq!(config.jittered_backoff(2, None), Duration::from_millis(400));
+        assert_eq!(config.jittered_backoff(10, None), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn full_jitter_stays_below_exponential_ceiling() {
+        let config = config(JitterMode::Full);
+
+        for _ in 0..100 {
+            assert!(config.jittered_backoff(2, None) <= Duration::from_millis(400));
+            assert!(config.jittered_backoff(10, None) <= Duration::from_millis(1000));
+        }
+    }
+
+    #[test]
+    fn decorrelated_jitter_grows_from_previous_backoff_within_bounds() {
+        let config = config(JitterMode::Decorrelated);
+
+        assert_eq!(config.jittered_backoff(0, None), Duration::from_millis(100));
+
+        for _ in 0..100 {
+            let backoff = config.jittered_backoff(1, Some(Duration::from_millis(200)));
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(600));
+
+            let capped = config.jittered_backoff(5, Some(Duration::from_millis(900)));
+            assert!(capped <= Duration::from_millis(1000));
+        }
+    }
 }
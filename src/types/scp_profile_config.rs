@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use super::nf_profile::{Ipv4AddressRange, NfProfile, PlmnId, ScpInfo};
+
+pub const DEFAULT_SCP_CAPABILITY: &str = "INDIRECT_COM_WITH_DELEG_DISC";
+
+#[derive(Debug, Clone)]
+pub struct ScpProfileConfig {
+    pub fqdn: Option<String>,
+    pub ipv4_addresses: Vec<String>,
+    pub scheme: String,
+    pub port: u16,
+    pub capacity: u32,
+    pub priority: u32,
+    pub locality: Option<String>,
+    pub plmn_list: Vec<PlmnId>,
+    pub nf_set_ids: Vec<String>,
+    pub scp_domains: Vec<String>,
+    pub scp_prefix: Option<String>,
+    pub served_nf_set_ids: Vec<String>,
+    pub address_domains: Vec<String>,
+    pub ipv4_addr_ranges: Vec<Ipv4AddressRange>,
+    pub remote_plmns: Vec<PlmnId>,
+    pub capabilities: Vec<String>,
+}
+
+impl Default for ScpProfileConfig {
+    fn default() -> Self {
+        Self {
+            fqdn: None,
+            ipv4_addresses: vec!["127.0.0.1".to_string()],
+            scheme: "http".to_string(),
+            port: 7777,
+            capacity: 100,
+            priority: 1,
+            locality: None,
+            plmn_list: Vec::new(),
+            nf_set_ids: Vec::new(),
+            scp_domains: Vec::new(),
+            scp_prefix: None,
+            served_nf_set_ids: Vec::new(),
+            address_domains: Vec::new(),
+            ipv4_addr_ranges: Vec::new(),
+            remote_plmns: Vec::new(),
+            capabilities: vec![DEFAULT_SCP_CAPABILITY.to_string()],
+        }
+    }
+}
+
+impl ScpProfileConfig {
+    pub fn to_nf_profile(&self, nf_instance_id: &str, heart_beat_timer: u32) -> NfProfile {
+        let scp_info = ScpInfo {
+            scp_prefix: self.scp_prefix.clone(),
+            scp_ports: BTreeMap::from([(self.scheme.clone(), self.port)]),
+            address_domains: self.address_domains.clone(),
+            ipv4_addresses: Vec::new(),
+            ipv4_addr_ranges: self.ipv4_addr_ranges.clone(),
+            served_nf_set_id_list: self.served_nf_set_ids.clone(),
+            remote_plmn_list: self.remote_plmns.clone(),
+            scp_capabilities: self.capabilities.clone(),
+        };
+
+        NfProfile {
+            nf_instance_id: nf_instance_id.to_string(),
+            nf_type: "SCP".to_string(),
+            nf_status: "REGISTERED".to_string(),
+            ipv4_addresses: self.ipv4_addresses.clone(),
+            fqdn: self.fqdn.clone(),
+            capacity: Some(self.capacity),
+            priority: Some(self.priority),
+            heart_beat_timer: Some(heart_beat_timer),
+            plmn_list: self.plmn_list.clone(),
+            locality: self.locality.clone(),
+            nf_set_id_list: self.nf_set_ids.clone(),
+            scp_domains: self.scp_domains.clone(),
+            scp_info: Some(scp_info),
+            nf_services: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct NextHopScpConfig {
+    pub uri: String,
+    pub priority: u32,
+    pub scp_domains: Vec<String>,
+    pub address_domains: Vec<String>,
+}
+
+impl NextHopScpConfig {
+    pub fn parse(entry: &str, default_priority: u32) -> Option<Self> {
+        let mut parts = entry.split(';');
+        let uri = parts.next()?.trim().trim_end_matches('/');
+        url::Url::parse(uri).ok()?;
+
+        let mut next_hop = Self {
+            uri: uri.to_string(),
+            priority: default_priority,
+            scp_domains: Vec::new(),
+            address_domains: Vec::new(),
+        };
+
+        for parameter in parts {
+            let (key, value) = parameter.split_once('=')?;
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "priority" => next_hop.priority = value.parse().ok()?,
+                "domain" => next_hop.scp_domains.push(value.to_string()),
+                "addressdomain" => next_hop.address_domains.push(value.to_lowercase()),
+                _ => return None,
+            }
+        }
+
+        Some(next_hop)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScpRoutingConfig {
+    pub next_hops: Vec<NextHopScpConfig>,
+    pub nrf_discovery: bool,
+    pub discovery_interval_seconds: u64,
+    pub unhealthy_threshold: u32,
+    pub unhealthy_seconds: u64,
+    pub max_hops: usize,
+}
+
+impl Default for ScpRoutingConfig {
+    fn default() -> Self {
+        Self {
+            next_hops: Vec::new(),
+            nrf_discovery: false,
+            discovery_interval_seconds: 60,
+            unhealthy_threshold: 3,
+            unhealthy_seconds: 30,
+            max_hops: 8,
+        }
+    }
+}
+
+impl ScpRoutingConfig {
+    pub fn discovery_interval(&self) -> Duration {
+        Duration::from_secs(self.discovery_interval_seconds.max(1))
+    }
+
+    pub fn unhealthy_duration(&self) -> Duration {
+        Duration::from_secs(self.unhealthy_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_uri_priority_and_domains() {
+        let next_hop = NextHopScpConfig::parse(
+            "http://scp-b.example:7777/;priority=2;domain=east;domain=west;addressdomain=Operator.COM",
+            10,
+        )
+        .unwrap();
+
+        assert_eq!(next_hop.uri, "http://scp-b.example:7777");
+        assert_eq!(next_hop.priority, 2);
+        assert_eq!(next_hop.scp_domains, vec!["east", "west"]);
+        assert_eq!(next_hop.address_domains, vec!["operator.com"]);
+    }
+
+    #[test]
+    fn parse_uses_default_priority() {
+        let next_hop = NextHopScpConfig::parse("https://scp-c.example", 10).unwrap();
+
+        assert_eq!(next_hop.priority, 10);
+        assert!(next_hop.scp_domains.is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_entries() {
+        assert!(NextHopScpConfig::parse("scp-b.example", 10).is_none());
+        assert!(NextHopScpConfig::parse("http://scp-b.example;priority=high", 10).is_none());
+        assert!(NextHopScpConfig::parse("http://scp-b.example;weight=1", 10).is_none());
+        assert!(NextHopScpConfig::parse("http://scp-b.example;domain", 10).is_none());
+    }
+}
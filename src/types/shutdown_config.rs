@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NrfShutdownAction {
+    #[default]
+    Deregister,
+    Undiscoverable,
+}
+
+impl NrfShutdownAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "deregister" => Some(NrfShutdownAction::Deregister),
+            "undiscoverable" => Some(NrfShutdownAction::Undiscoverable),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ShutdownConfig {
+    pub nrf_action: NrfShutdownAction,
+    pub grace_period_seconds: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            nrf_action: NrfShutdownAction::Deregister,
+            grace_period_seconds: 30,
+        }
+    }
+}
+
+impl ShutdownConfig {
+    pub fn grace_period(&self) -> Duration {
+        Duration::from_secs(self.grace_period_seconds)
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StateStoreBackend {
+    #[default]
+    Memory,
+    MongoDb,
+}
+
+impl StateStoreBackend {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "memory" => Some(StateStoreBackend::Memory),
+            "mongodb" | "mongo" => Some(StateStoreBackend::MongoDb),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StateStoreConfig {
+    pub backend: StateStoreBackend,
+    pub binding_ttl_seconds: u64,
+    pub write_interval_seconds: u64,
+    pub negative_cache_seconds: u64,
+}
+
+impl Default for StateStoreConfig {
+    fn default() -> Self {
+        Self {
+            backend: StateStoreBackend::Memory,
+            binding_ttl_seconds: 3600,
+            write_interval_seconds: 30,
+            negative_cache_seconds: 5,
+        }
+    }
+}
+
+impl StateStoreConfig {
+    pub fn binding_ttl(&self) -> Duration {
+        Duration::from_secs(self.binding_ttl_seconds)
+    }
+
+    pub fn write_interval(&self) -> Duration {
+        Duration::from_secs(self.write_interval_seconds)
+    }
+
+    pub fn negative_cache(&self) -> Duration {
+        Duration::from_secs(self.negative_cache_seconds)
+    }
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct StickySessionConfig {
+    pub ttl_seconds: u64,
+    pub max_entries: usize,
+    pub sweep_interval_seconds: u64,
+}
+
+impl Default for StickySessionConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 300,
+            max_entries: 100_000,
+            sweep_interval_seconds: 60,
+        }
+    }
+}
+
+impl StickySessionConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_seconds)
+    }
+
+    pub fn sweep_interval(&self) -> Duration {
+        Duration::from_secs(self.sweep_interval_seconds.max(1))
+    }
+}
@@ -1,3 +1,6 @@
+pub mod deadline;
 pub mod retry;
+pub mod sbi_headers;
 
+pub use deadline::*;
 pub use retry::*;
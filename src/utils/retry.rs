@@ -1,31 +1,72 @@
 use std::future::Future;
+use std::time::Duration;
 use crate::types::RetryConfig;
+use super::Deadline;
 
-pub async fn retry_with_backoff<F, Fut, T, E>(
+pub enum RetryDecision {
+    Retry,
+    RetryAfter(Duration),
+    Abort,
+}
+
+pub async fn retry_with_backoff<F, Fut, T, E, C, A>(
     retry_config: &RetryConfig,
+    deadline: Option<&Deadline>,
+    mut classify: C,
+    mut admit_retry: A,
     mut operation: F,
 ) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: Future<Output = Result<T, E>>,
+    C: FnMut(&E) -> RetryDecision,
+    A: FnMut() -> bool,
 {
     let mut last_error = None;
+    let mut previous_backoff = None;
 
     for attempt in 0..retry_config.max_attempts {
         match operation().await {
             Ok(result) => return Ok(result),
             Err(e) => {
-                last_error = Some(e);
-
                 if attempt + 1 < retry_config.max_attempts {
-                    let backoff = retry_config.calculate_backoff(attempt);
+                    let decision = classify(&e);
+                    last_error = Some(e);
+
+                    let backoff = match decision {
+                        RetryDecision::Retry => retry_config.jittered_backoff(attempt, previous_backoff),
+                        RetryDecision::RetryAfter(retry_after) => retry_after,
+                        RetryDecision::Abort => {
+                            tracing::debug!("Request failed with a non-retryable error");
+                            break;
+                        }
+                    };
+
+                    if let Some(deadline) = deadline {
+                        if backoff >= deadline.remaining() {
+                            tracing::debug!(
+                                "Not retrying, backoff {:?} exceeds remaining budget {:?}",
+                                backoff,
+                                deadline.remaining()
+                            );
+                            break;
+                        }
+                    }
+
+                    if !admit_retry() {
+                        break;
+                    }
+
                     tracing::debug!(
                         "Request failed, retrying in {:?} (attempt {}/{})",
                         backoff,
                         attempt + 1,
                         retry_config.max_attempts
                     );
+                    previous_backoff = Some(backoff);
                     tokio::time::sleep(backoff).await;
+                } else {
+                    last_error = Some(e);
                 }
             }
         }
@@ -33,3 +74,77 @@ where
 
     Err(last_error.unwrap())
 }
+
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = retry_at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[tokio::test]
+    async fn retry_is_not_admitted_when_backoff_exceeds_deadline() {
+        let retry_config = RetryConfig {
+            initial_backoff_ms: 500,
+            jitter: crate::types::JitterMode::None,
+            ..RetryConfig::default()
+        };
+        let deadline = Deadline::after(Duration::from_millis(100));
+        let admitted = Cell::new(0);
+        let attempts = Cell::new(0);
+
+        let result: Result<(), ()> = retry_with_backoff(
+            &retry_config,
+            Some(&deadline),
+            |_| RetryDecision::Retry,
+            || {
+                admitted.set(admitted.get() + 1);
+                true
+            },
+            || {
+                attempts.set(attempts.get() + 1);
+                async { Err(()) }
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+        assert_eq!(admitted.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn denied_admission_stops_retrying() {
+        let retry_config = RetryConfig {
+            initial_backoff_ms: 1,
+            ..RetryConfig::default()
+        };
+        let attempts = Cell::new(0);
+
+        let result: Result<(), ()> = retry_with_backoff(
+            &retry_config,
+            None,
+            |_| RetryDecision::Retry,
+            || false,
+            || {
+                attempts.set(attempts.get() + 1);
+                async { Err(()) }
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+}
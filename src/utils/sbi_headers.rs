@@ -0,0 +1,198 @@
+use axum::http::HeaderMap;
+use std::collections::HashMap;
+
+pub const SBI_TARGET_API_ROOT: &str = "3gpp-sbi-target-apiroot";
+pub const SBI_CALLBACK: &str = "3gpp-sbi-callback";
+pub const SBI_MAX_RSP_TIME: &str = "3gpp-sbi-max-rsp-time";
+pub const SBI_BINDING: &str = "3gpp-sbi-binding";
+pub const SBI_ROUTING_BINDING: &str = "3gpp-sbi-routing-binding";
+pub const SBI_PRODUCER_ID: &str = "3gpp-sbi-producer-id";
+pub const SBI_TARGET_NF_ID: &str = "3gpp-sbi-target-nf-id";
+pub const SBI_SELECTION_INFO: &str = "3gpp-sbi-selection-info";
+pub const SBI_MESSAGE_PRIORITY: &str = "3gpp-sbi-message-priority";
+pub const SBI_LCI: &str = "3gpp-sbi-lci";
+pub const SBI_OCI: &str = "3gpp-sbi-oci";
+pub const VIA: &str = "via";
+
+pub fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn target_api_root(headers: &HeaderMap) -> Option<String> {
+    header_str(headers, SBI_TARGET_API_ROOT).map(|value| value.trim_end_matches('/').to_string())
+}
+
+pub fn callback_type(headers: &HeaderMap) -> Option<String> {
+    let value = header_str(headers, SBI_CALLBACK)?;
+
+    let callback_type = value
+        .split(';')
+        .next()?
+        .trim()
+        .trim_matches('"');
+
+    if callback_type.is_empty() {
+        None
+    } else {
+        Some(callback_type.to_string())
+    }
+}
+
+pub fn max_rsp_time_ms(headers: &HeaderMap) -> Option<u64> {
+    header_str(headers, SBI_MAX_RSP_TIME)?
+        .parse()
+        .ok()
+        .filter(|ms| *ms > 0)
+}
+
+pub fn message_priority(headers: &HeaderMap) -> Option<u8> {
+    header_str(headers, SBI_MESSAGE_PRIORITY)?
+        .parse()
+        .ok()
+        .filter(|priority| *priority <= 31)
+}
+
+pub fn lci(timestamp: &str, scp_identity: &str, load_percent: u32) -> String {
+    format!(
+        "Timestamp: \"{}\"; {}; Load-Metric: {}%",
+        timestamp, scp_identity, load_percent
+    )
+}
+
+pub fn oci(timestamp: &str, scp_identity: &str, validity_seconds: u64, reduction_percent: u32) -> String {
+    format!(
+        "Timestamp: \"{}\"; Period-of-Validity: {}s; Overload-Reduction-Metric: {}%; {}",
+        timestamp, validity_seconds, reduction_percent, scp_identity
+    )
+}
+
+pub fn binding_parameters(value: &str) -> HashMap<String, String> {
+    value
+        .split(';')
+        .filter_map(|parameter| {
+            let (key, value) = parameter.split_once('=')?;
+            let key = key.trim().to_lowercase();
+            let value = value.trim().trim_matches('"');
+            if key.is_empty() || value.is_empty() {
+                return None;
+            }
+            Some((key, value.to_string()))
+        })
+        .collect()
+}
+
+pub fn routing_binding_nf_instance(headers: &HeaderMap) -> Option<String> {
+    let value = header_str(headers, SBI_ROUTING_BINDING)?;
+    binding_parameters(value).remove("nfinst")
+}
+
+pub fn via_received_by(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(VIA)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|entry| entry.split_whitespace().nth(1))
+        .map(|received_by| received_by.to_string())
+        .collect()
+}
+
+pub fn nf_identity(nf_instance_id: &str, nf_service_instance_id: Option<&str>, nf_set_id: Option<&str>) -> String {
+    let mut value = format!("nfinst={}", nf_instance_id);
+    if let Some(nf_service_instance_id) = nf_service_instance_id {
+        value.push_str(&format!("; nfservinst={}", nf_service_instance_id));
+    }
+    if let Some(nf_set_id) = nf_set_id {
+        value.push_str(&format!("; nfset={}", nf_set_id));
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn message_priority_accepts_values_up_to_thirty_one() {
+        assert_eq!(message_priority(&headers(SBI_MESSAGE_PRIORITY, "0")), Some(0));
+        assert_eq!(message_priority(&headers(SBI_MESSAGE_PRIORITY, " 31 ")), Some(31));
+        assert_eq!(message_priority(&headers(SBI_MESSAGE_PRIORITY, "32")), None);
+        assert_eq!(message_priority(&headers(SBI_MESSAGE_PRIORITY, "high")), None);
+        assert_eq!(message_priority(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn load_and_overload_control_headers_are_formatted() {
+        let timestamp = "Mon, 19 Oct 2026 10:00:00 GMT";
+
+        assert_eq!(
+            lci(timestamp, "SCP-scp.example", 42),
+            "Timestamp: \"Mon, 19 Oct 2026 10:00:00 GMT\"; SCP-scp.example; Load-Metric: 42%"
+        );
+        assert_eq!(
+            oci(timestamp, "SCP-scp.example", 30, 25),
+            "Timestamp: \"Mon, 19 Oct 2026 10:00:00 GMT\"; Period-of-Validity: 30s; \
+             Overload-Reduction-Metric: 25%; SCP-scp.example"
+        );
+    }
+
+    #[test]
+    fn via_received_by_collects_every_hop() {
+        let mut headers = headers(VIA, "2.0 SCP-scp-a.example, 2.0 SCP-scp-b.example");
+        headers.append(VIA, HeaderValue::from_static("HTTP/2.0 SCP-scp-c.example"));
+        headers.append(VIA, HeaderValue::from_static("malformed"));
+
+        assert_eq!(
+            via_received_by(&headers),
+            vec!["SCP-scp-a.example", "SCP-scp-b.example", "SCP-scp-c.example"]
+        );
+        assert!(via_received_by(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn max_rsp_time_requires_positive_milliseconds() {
+        assert_eq!(max_rsp_time_ms(&headers(SBI_MAX_RSP_TIME, "1500")), Some(1500));
+        assert_eq!(max_rsp_time_ms(&headers(SBI_MAX_RSP_TIME, " 20 ")), Some(20));
+        assert_eq!(max_rsp_time_ms(&headers(SBI_MAX_RSP_TIME, "0")), None);
+        assert_eq!(max_rsp_time_ms(&headers(SBI_MAX_RSP_TIME, "-5")), None);
+        assert_eq!(max_rsp_time_ms(&headers(SBI_MAX_RSP_TIME, "1.5")), None);
+        assert_eq!(max_rsp_time_ms(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn binding_parameters_are_parsed_case_insensitively() {
+        let parameters =
+            binding_parameters("BL=nfinstance; nfinst=\"54804518-4191-46b3-955c-ac631f953ed8\"; nfset=set1; empty=; junk");
+
+        assert_eq!(parameters.get("bl").map(String::as_str), Some("nfinstance"));
+        assert_eq!(
+            parameters.get("nfinst").map(String::as_str),
+            Some("54804518-4191-46b3-955c-ac631f953ed8")
+        );
+        assert_eq!(parameters.get("nfset").map(String::as_str), Some("set1"));
+        assert_eq!(parameters.len(), 3);
+    }
+
+    #[test]
+    fn routing_binding_yields_the_bound_nf_instance() {
+        assert_eq!(
+            routing_binding_nf_instance(&headers(SBI_ROUTING_BINDING, "bl=nfinstance; nfinst=udm-1")),
+            Some("udm-1".to_string())
+        );
+        assert_eq!(
+            routing_binding_nf_instance(&headers(SBI_ROUTING_BINDING, "bl=nfset; nfset=set1")),
+            None
+        );
+    }
+}